@@ -0,0 +1,479 @@
+//! Minimal SQL tokenizer used to locate bind placeholders.
+//!
+//! This is not a full SQL parser: it only knows enough about the lexical structure of SQL to
+//! tell placeholders apart from text that merely looks like one, e.g. a `$1` inside a string
+//! literal, a quoted identifier, a comment or a dollar-quoted function body.
+//!
+//! It is shared by `sqlx-fragment` and `sqlx-fragment-macros`, for the compile-time checks of
+//! `fragment!()`, so that both crates read SQL the same way. It is an implementation detail of
+//! these crates, with no stability guarantees.
+
+/// The kind of a [`Token`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Plain SQL text (keywords, identifiers, operators, whitespace, ...).
+    Sql,
+    /// A string literal, quoted identifier or dollar-quoted string, including its delimiters.
+    Quoted,
+    /// A `-- line`, `# line` (MySQL) or `/* block */` comment.
+    Comment,
+    /// A numbered placeholder, e.g. `$3`.
+    Numbered(usize),
+    /// A `?` placeholder (or operator, on backends that don't use it for binds).
+    Question,
+    /// A named parameter, e.g. `:name`, which is not a placeholder syntax of any backend.
+    /// The text of the token includes the leading `:`.
+    Named,
+}
+
+/// A slice of SQL text with its [`TokenKind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// Whether a literal or comment is closed before the end of the input. Always `true` for
+    /// other tokens.
+    pub terminated: bool,
+}
+
+/// The lexical rules of a SQL dialect, as far as telling placeholders apart from literals and
+/// comments is concerned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Syntax {
+    /// `\` escapes the next character in string literals (MySQL).
+    pub backslash_escapes: bool,
+    /// `#` starts a line comment (MySQL).
+    pub hash_comments: bool,
+    /// `$tag$ ... $tag$` strings, `E'...'` strings with backslash escapes and nested block
+    /// comments (Postgres).
+    pub dollar_quotes: bool,
+}
+
+/// Iterator over the [`Token`]s of a SQL string.
+///
+/// Concatenating the `text` of every token yields the original string back. Unterminated
+/// literals and comments extend to the end of the input, and are flagged as such.
+pub struct Lexer<'a> {
+    sql: &'a str,
+    pos: usize,
+    syntax: Syntax,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(sql: &'a str, syntax: Syntax) -> Self {
+        Lexer {
+            sql,
+            pos: 0,
+            syntax,
+        }
+    }
+
+    fn bytes(&self) -> &'a [u8] {
+        self.sql.as_bytes()
+    }
+
+    fn peek(&self, offset: usize) -> Option<u8> {
+        self.bytes().get(self.pos + offset).copied()
+    }
+
+    /// Whether the byte before the current position belongs to an identifier.
+    fn follows_ident(&self) -> bool {
+        self.pos > 0 && is_ident_byte(self.bytes()[self.pos - 1])
+    }
+
+    /// Kind, length and termination of the token starting at the current position, if it is
+    /// not plain SQL.
+    fn special_token(&self) -> Option<(TokenKind, usize, bool)> {
+        let rest = &self.bytes()[self.pos..];
+        let syntax = self.syntax;
+
+        match rest[0] {
+            b'\'' => {
+                // `E'...'` strings accept backslash escapes.
+                let e_string = syntax.dollar_quotes
+                    && self.pos > 0
+                    && matches!(self.bytes()[self.pos - 1], b'e' | b'E')
+                    && (self.pos < 2 || !is_ident_byte(self.bytes()[self.pos - 2]));
+                let (len, terminated) =
+                    quoted_len(rest, b'\'', syntax.backslash_escapes || e_string);
+                Some((TokenKind::Quoted, len, terminated))
+            }
+            b'"' => {
+                let (len, terminated) = quoted_len(rest, b'"', syntax.backslash_escapes);
+                Some((TokenKind::Quoted, len, terminated))
+            }
+            b'`' => {
+                let (len, terminated) = quoted_len(rest, b'`', false);
+                Some((TokenKind::Quoted, len, terminated))
+            }
+            b'-' if rest.get(1) == Some(&b'-') => {
+                Some((TokenKind::Comment, line_comment_len(rest), true))
+            }
+            b'#' if syntax.hash_comments => {
+                Some((TokenKind::Comment, line_comment_len(rest), true))
+            }
+            b'/' if rest.get(1) == Some(&b'*') => {
+                let (len, terminated) = block_comment_len(rest, syntax.dollar_quotes);
+                Some((TokenKind::Comment, len, terminated))
+            }
+            b'?' => Some((TokenKind::Question, 1, true)),
+            // Not a `::` cast, nor a slice such as `array[lo:hi]`.
+            b':' if !self.follows_ident()
+                && (self.pos == 0 || self.bytes()[self.pos - 1] != b':')
+                && rest
+                    .get(1)
+                    .is_some_and(|&b| b.is_ascii_alphabetic() || b == b'_') =>
+            {
+                let len = 1 + rest[1..]
+                    .iter()
+                    .take_while(|&&b| b.is_ascii_alphanumeric() || b == b'_')
+                    .count();
+                Some((TokenKind::Named, len, true))
+            }
+            b'$' if !self.follows_ident() => {
+                let digits = rest[1..].iter().take_while(|b| b.is_ascii_digit()).count();
+                if digits > 0 {
+                    let number = self.sql[self.pos + 1..self.pos + 1 + digits].parse().ok()?;
+                    return Some((TokenKind::Numbered(number), 1 + digits, true));
+                }
+
+                if !syntax.dollar_quotes {
+                    return None;
+                }
+                dollar_quoted_len(rest)
+                    .map(|(len, terminated)| (TokenKind::Quoted, len, terminated))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        if self.pos >= self.sql.len() {
+            return None;
+        }
+
+        let start = self.pos;
+        let (kind, len, terminated) = match self.special_token() {
+            Some(special) => special,
+            None => {
+                // Consume plain SQL up to the next byte that may start a special token.
+                self.pos += 1;
+                while self.pos < self.sql.len() {
+                    if matches!(
+                        self.peek(0),
+                        Some(b'\'' | b'"' | b'`' | b'-' | b'#' | b'/' | b'?' | b'$' | b':')
+                    ) && self.special_token().is_some()
+                    {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                (TokenKind::Sql, self.pos - start, true)
+            }
+        };
+
+        self.pos = start + len;
+
+        Some(Token {
+            kind,
+            text: &self.sql[start..self.pos],
+            terminated,
+        })
+    }
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80
+}
+
+/// Length of a literal delimited by `quote`, where a doubled delimiter is an escaped one, and
+/// whether it is terminated.
+fn quoted_len(rest: &[u8], quote: u8, backslash_escapes: bool) -> (usize, bool) {
+    let mut i = 1;
+    while i < rest.len() {
+        match rest[i] {
+            b'\\' if backslash_escapes => i += 2,
+            b if b == quote => {
+                if rest.get(i + 1) == Some(&quote) {
+                    i += 2;
+                } else {
+                    return (i + 1, true);
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    (rest.len(), false)
+}
+
+/// Length of a line comment, excluding the line break.
+fn line_comment_len(rest: &[u8]) -> usize {
+    rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len())
+}
+
+/// Length of a block comment, which may be nested as in Postgres, and whether it is
+/// terminated.
+fn block_comment_len(rest: &[u8], nested: bool) -> (usize, bool) {
+    let mut depth = 0;
+    let mut i = 0;
+    while i + 1 < rest.len() {
+        match (rest[i], rest[i + 1]) {
+            (b'/', b'*') if nested || depth == 0 => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', b'/') => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return (i, true);
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    (rest.len(), false)
+}
+
+/// Length of a `$tag$ ... $tag$` string and whether it is terminated, or `None` if `rest`
+/// doesn't start with a tag.
+fn dollar_quoted_len(rest: &[u8]) -> Option<(usize, bool)> {
+    let tag_len = 1 + rest[1..]
+        .iter()
+        .take_while(|&&b| is_ident_byte(b) && b != b'$')
+        .count();
+    if rest.get(tag_len) != Some(&b'$') || rest.get(1).is_some_and(u8::is_ascii_digit) {
+        return None;
+    }
+
+    let tag = &rest[..=tag_len];
+    let body = &rest[tag.len()..];
+    let len = body
+        .windows(tag.len())
+        .position(|window| window == tag)
+        .map_or((rest.len(), false), |end| {
+            (tag.len() + end + tag.len(), true)
+        });
+    Some(len)
+}
+
+/// Copy `sql` to `out`, adding `offset` to the number of every `$N` placeholder.
+///
+/// `$N` placeholders are only used by Postgres, so `sql` is read with its syntax.
+pub fn renumber_placeholders(sql: &str, offset: usize, out: &mut String) {
+    use std::fmt::Write;
+
+    let syntax = Syntax {
+        dollar_quotes: true,
+        ..Syntax::default()
+    };
+    for token in Lexer::new(sql, syntax) {
+        match token.kind {
+            TokenKind::Numbered(n) => {
+                write!(out, "${}", n + offset).expect("error writing placeholder");
+            }
+            _ => out.push_str(token.text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POSTGRES: Syntax = Syntax {
+        backslash_escapes: false,
+        hash_comments: false,
+        dollar_quotes: true,
+    };
+
+    const MYSQL: Syntax = Syntax {
+        backslash_escapes: true,
+        hash_comments: true,
+        dollar_quotes: false,
+    };
+
+    fn kinds(sql: &str, syntax: Syntax) -> Vec<(TokenKind, &str)> {
+        Lexer::new(sql, syntax)
+            .map(|token| (token.kind, token.text))
+            .collect()
+    }
+
+    fn renumber(sql: &str, offset: usize) -> String {
+        let mut out = String::new();
+        renumber_placeholders(sql, offset, &mut out);
+        out
+    }
+
+    #[test]
+    fn tokens_concatenate_to_input() {
+        let sql = "SELECT 'a''$1', E'\\'$2', \"$3\"\"\", /* /* $4 */ */ $tag$ $5 $tag$, $6 -- $7";
+        let text: String = Lexer::new(sql, POSTGRES).map(|token| token.text).collect();
+        assert_eq!(text, sql);
+    }
+
+    #[test]
+    fn string_literals() {
+        assert_eq!(
+            kinds("'it''s $1' $2", POSTGRES),
+            [
+                (TokenKind::Quoted, "'it''s $1'"),
+                (TokenKind::Sql, " "),
+                (TokenKind::Numbered(2), "$2"),
+            ]
+        );
+        // Without `E`, a backslash is an ordinary character.
+        assert_eq!(
+            kinds(r"'a\' $1", POSTGRES),
+            [
+                (TokenKind::Quoted, r"'a\'"),
+                (TokenKind::Sql, " "),
+                (TokenKind::Numbered(1), "$1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn e_strings() {
+        assert_eq!(
+            kinds(r"E'\' $1' $2", POSTGRES),
+            [
+                (TokenKind::Sql, "E"),
+                (TokenKind::Quoted, r"'\' $1'"),
+                (TokenKind::Sql, " "),
+                (TokenKind::Numbered(2), "$2"),
+            ]
+        );
+        // `e` at the end of an identifier doesn't start an E-string.
+        assert_eq!(
+            kinds(r"name'\' $1", POSTGRES)[1],
+            (TokenKind::Quoted, r"'\'")
+        );
+    }
+
+    #[test]
+    fn quoted_identifiers() {
+        assert_eq!(
+            kinds("\"a\"\"$1\" `$2` $3", POSTGRES),
+            [
+                (TokenKind::Quoted, "\"a\"\"$1\""),
+                (TokenKind::Sql, " "),
+                (TokenKind::Quoted, "`$2`"),
+                (TokenKind::Sql, " "),
+                (TokenKind::Numbered(3), "$3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_comments() {
+        assert_eq!(
+            kinds("/* a /* $1 */ $2 */ $3 -- $4\n$5", POSTGRES),
+            [
+                (TokenKind::Comment, "/* a /* $1 */ $2 */"),
+                (TokenKind::Sql, " "),
+                (TokenKind::Numbered(3), "$3"),
+                (TokenKind::Sql, " "),
+                (TokenKind::Comment, "-- $4"),
+                (TokenKind::Sql, "\n"),
+                (TokenKind::Numbered(5), "$5"),
+            ]
+        );
+        // Other backends don't nest comments.
+        assert_eq!(
+            kinds("/* /* */ $1", MYSQL)[0],
+            (TokenKind::Comment, "/* /* */")
+        );
+    }
+
+    #[test]
+    fn dollar_quoted_strings() {
+        assert_eq!(
+            kinds("$$ $1 $$ $fn$ $2 $$ $fn$ $3", POSTGRES),
+            [
+                (TokenKind::Quoted, "$$ $1 $$"),
+                (TokenKind::Sql, " "),
+                (TokenKind::Quoted, "$fn$ $2 $$ $fn$"),
+                (TokenKind::Sql, " "),
+                (TokenKind::Numbered(3), "$3"),
+            ]
+        );
+        // `$` within an identifier is neither a tag nor a placeholder.
+        assert_eq!(kinds("a$1 b$", POSTGRES), [(TokenKind::Sql, "a$1 b$")]);
+    }
+
+    #[test]
+    fn unterminated_tokens() {
+        for sql in ["'a", "\"a", "/* /* */", "$x$ a"] {
+            let tokens: Vec<_> = Lexer::new(sql, POSTGRES).collect();
+            assert_eq!(tokens.len(), 1, "{sql}");
+            assert_eq!(tokens[0].text, sql);
+            assert!(!tokens[0].terminated, "{sql}");
+        }
+    }
+
+    #[test]
+    fn renumber_skips_literals_and_comments() {
+        assert_eq!(
+            renumber(
+                "a = $1 AND b = '$1' AND c = E'\\'$2' AND \"$3\" = $2 /* $1 /* */ $1 */ $fn$ $1 $fn$ -- $1",
+                3
+            ),
+            "a = $4 AND b = '$1' AND c = E'\\'$2' AND \"$3\" = $5 /* $1 /* */ $1 */ $fn$ $1 $fn$ -- $1"
+        );
+        assert_eq!(renumber("a::INT4 = $10", 0), "a::INT4 = $10");
+        assert_eq!(renumber("a = $9", 1), "a = $10");
+    }
+
+    #[test]
+    fn mysql_backslash_escapes() {
+        assert_eq!(
+            kinds(r"a = 'it\'s ?' AND b = ?", MYSQL),
+            [
+                (TokenKind::Sql, "a = "),
+                (TokenKind::Quoted, r"'it\'s ?'"),
+                (TokenKind::Sql, " AND b = "),
+                (TokenKind::Question, "?"),
+            ]
+        );
+        assert_eq!(
+            kinds(r#""a\"?" ?"#, MYSQL),
+            [
+                (TokenKind::Quoted, r#""a\"?""#),
+                (TokenKind::Sql, " "),
+                (TokenKind::Question, "?"),
+            ]
+        );
+    }
+
+    #[test]
+    fn mysql_hash_comments() {
+        assert_eq!(
+            kinds("SELECT 1 # :foo ?\n WHERE a = :a", MYSQL),
+            [
+                (TokenKind::Sql, "SELECT 1 "),
+                (TokenKind::Comment, "# :foo ?"),
+                (TokenKind::Sql, "\n WHERE a = "),
+                (TokenKind::Named, ":a"),
+            ]
+        );
+    }
+
+    #[test]
+    fn standard_syntax_has_no_mysql_rules() {
+        assert_eq!(
+            kinds(r"'a\' ? # ?", Syntax::default()),
+            [
+                (TokenKind::Quoted, r"'a\'"),
+                (TokenKind::Sql, " "),
+                (TokenKind::Question, "?"),
+                (TokenKind::Sql, " # "),
+                (TokenKind::Question, "?"),
+            ]
+        );
+    }
+}
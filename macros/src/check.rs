@@ -0,0 +1,209 @@
+//! Compile-time sanity checks for the SQL text of `fragment!()`.
+//!
+//! The checks only rely on the lexical grammar of the dialect selected with the `postgres`,
+//! `mysql` and `sqlite` features, so they don't need a database connection. They are
+//! deliberately conservative: a fragment is usually not a complete statement, so anything
+//! beyond tokens and parentheses is left to the database.
+
+use crate::lexer::{Lexer, Syntax, TokenKind};
+
+/// Stands for a `{...}` placeholder in the text handed to [`check_sql()`].
+pub(crate) const HOLE: char = '\0';
+
+/// The rules of the selected dialect(s).
+struct Dialect {
+    /// The lexical rules, combined so that a fragment is read the way every selected backend
+    /// reads it.
+    syntax: Syntax,
+    /// `$N` is a bind placeholder.
+    dollar_placeholders: bool,
+    /// `?` is a bind placeholder (it's an operator in Postgres).
+    question_placeholders: bool,
+}
+
+impl Dialect {
+    fn selected() -> Self {
+        let postgres = cfg!(feature = "postgres");
+        let mysql = cfg!(feature = "mysql");
+        let sqlite = cfg!(feature = "sqlite");
+        let any = postgres || mysql || sqlite;
+
+        Dialect {
+            syntax: Syntax {
+                backslash_escapes: mysql,
+                hash_comments: mysql,
+                dollar_quotes: postgres,
+            },
+            dollar_placeholders: postgres || sqlite || !any,
+            question_placeholders: !postgres,
+        }
+    }
+}
+
+/// Check `sql` for problems that are certain to produce a broken query.
+///
+/// Returns a description of the first problem found.
+pub(crate) fn check_sql(sql: &str) -> Result<(), String> {
+    let dialect = Dialect::selected();
+    let mut open_parens = Vec::new();
+    let mut start = 0;
+
+    for token in Lexer::new(sql, dialect.syntax) {
+        match token.kind {
+            TokenKind::Quoted | TokenKind::Comment if !token.terminated => {
+                let what = match token.text.as_bytes()[0] {
+                    b'/' => "block comment",
+                    b'$' => "dollar-quoted string",
+                    _ => "quote",
+                };
+                return Err(format!(
+                    "unterminated {what} starting at `{}`",
+                    excerpt(sql, start)
+                ));
+            }
+            TokenKind::Quoted | TokenKind::Comment if token.text.contains(HOLE) => {
+                return Err(format!(
+                    "placeholder inside a quoted string or comment at `{}`; \
+                     it would not be replaced by a bind argument",
+                    excerpt(sql, start)
+                ));
+            }
+            TokenKind::Numbered(_) if dialect.dollar_placeholders => {
+                return Err(format!(
+                    "literal placeholder `{}` would collide with bind arguments; \
+                     use `{{}}` placeholders instead",
+                    token.text
+                ));
+            }
+            TokenKind::Question if dialect.question_placeholders => {
+                return Err(format!(
+                    "literal `?` would collide with bind arguments; \
+                     use `{{}}` placeholders instead (near `{}`)",
+                    excerpt(sql, start)
+                ));
+            }
+            TokenKind::Sql => {
+                for (offset, b) in token.text.bytes().enumerate() {
+                    match b {
+                        b'(' => open_parens.push(start + offset),
+                        b')' if open_parens.pop().is_none() => {
+                            return Err(format!(
+                                "unmatched `)` at `{}`",
+                                excerpt(sql, start + offset)
+                            ));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        start += token.text.len();
+    }
+
+    match open_parens.first() {
+        Some(&open) => Err(format!("unclosed `(` at `{}`", excerpt(sql, open))),
+        None => Ok(()),
+    }
+}
+
+/// A short excerpt of `sql` starting at byte `start`, for error messages.
+fn excerpt(sql: &str, start: usize) -> String {
+    sql[start..]
+        .chars()
+        .take(20)
+        .map(|c| if c == HOLE { '…' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(sql: &str) -> Result<(), String> {
+        check_sql(&sql.replace("{}", &HOLE.to_string()))
+    }
+
+    #[test]
+    fn accepts_holes_outside_literals() {
+        assert_eq!(check("a = {} AND (b IN ({}, {}) OR c = 'x')"), Ok(()));
+        assert_eq!(check("\"weird)(\" = {} -- (\n/* ( */"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_unbalanced_parens() {
+        assert_eq!(check("(a = {}"), Err("unclosed `(` at `(a = …`".to_owned()));
+        assert_eq!(check("a = {})"), Err("unmatched `)` at `)`".to_owned()));
+    }
+
+    #[test]
+    fn rejects_unterminated_literals_and_comments() {
+        assert_eq!(
+            check("a = 'x"),
+            Err("unterminated quote starting at `'x`".to_owned())
+        );
+        assert_eq!(
+            check("a = \"x"),
+            Err("unterminated quote starting at `\"x`".to_owned())
+        );
+        assert_eq!(
+            check("a /* x"),
+            Err("unterminated block comment starting at `/* x`".to_owned())
+        );
+    }
+
+    #[test]
+    fn rejects_holes_in_literals_and_comments() {
+        for sql in ["a = '{}'", "a = \"{}\"", "a = 1 -- {}", "a = 1 /* {} */"] {
+            assert!(
+                check(sql)
+                    .unwrap_err()
+                    .starts_with("placeholder inside a quoted string or comment"),
+                "{sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn literal_placeholders() {
+        let dollar = check("a = $1");
+        let question = check("a = ?");
+
+        if cfg!(any(
+            feature = "postgres",
+            feature = "sqlite",
+            not(feature = "mysql")
+        )) {
+            assert!(dollar.unwrap_err().starts_with("literal placeholder `$1`"));
+        } else {
+            assert_eq!(dollar, Ok(()));
+        }
+
+        if cfg!(feature = "postgres") {
+            assert_eq!(question, Ok(()));
+        } else {
+            assert!(question.unwrap_err().starts_with("literal `?`"));
+        }
+
+        assert_eq!(check("'$1 ?' a$1"), Ok(()));
+    }
+
+    #[test]
+    fn dialect_specific_literals() {
+        if cfg!(feature = "postgres") {
+            assert_eq!(check("$$ ' $$ = {} /* /* */ */ E'\\''"), Ok(()));
+            assert!(check("$tag$ x")
+                .unwrap_err()
+                .starts_with("unterminated dollar-quoted string"));
+        }
+        if cfg!(feature = "mysql") {
+            assert_eq!(
+                check("'it\\'s' = {} # {}"),
+                Err("placeholder inside a quoted string or comment at `# …`; \
+                 it would not be replaced by a bind argument"
+                    .to_owned())
+            );
+        }
+    }
+}
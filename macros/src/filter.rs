@@ -0,0 +1,187 @@
+//! Implementation of `#[derive(Filter)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Fields, GenericArgument, LitStr, PathArguments, Type};
+
+/// The options of a `#[filter(...)]` field attribute.
+#[derive(Default)]
+struct FieldOptions {
+    column: Option<String>,
+    op: Option<LitStr>,
+    skip: bool,
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("filter"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("column") {
+                options.column = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("op") {
+                options.op = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `column = \"...\"`, `op = \"...\"` or `skip`"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
+/// The type argument of `ty` if it is `name<T>`, e.g. `Option<T>` or `std::vec::Vec<T>`.
+fn type_argument<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(ty) if arguments.args.len() == 1 => Some(ty),
+        _ => None,
+    }
+}
+
+pub(crate) fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+    let ident = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`Filter` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`Filter` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut conditions = Vec::new();
+    let mut bound_types = Vec::new();
+    for field in fields {
+        let options = field_options(field)?;
+        if options.skip {
+            continue;
+        }
+
+        let member = field.ident.as_ref().expect("named field");
+        let column = options
+            .column
+            .unwrap_or_else(|| member.to_string().trim_start_matches("r#").to_owned());
+
+        let (value_ty, optional) = match type_argument(&field.ty, "Option") {
+            Some(inner) => (inner, true),
+            None => (&field.ty, false),
+        };
+        let element_ty = type_argument(value_ty, "Vec");
+
+        let op = options
+            .op
+            .as_ref()
+            .map(|op| op.value().trim().to_uppercase());
+        let push = match (element_ty, op.as_deref()) {
+            (Some(element_ty), None | Some("IN" | "NOT IN")) => {
+                let method = match op.as_deref() {
+                    Some("NOT IN") => quote!(push_not_in),
+                    _ => quote!(push_in),
+                };
+                bound_types.push(element_ty);
+                quote! {
+                    fragment.#method(#column, ::std::iter::Iterator::cloned(value.iter()));
+                }
+            }
+            (None, Some("IN" | "NOT IN")) => {
+                return Err(syn::Error::new_spanned(
+                    &options.op,
+                    "`IN` and `NOT IN` can only be used with `Vec` fields",
+                ))
+            }
+            _ => {
+                let op = options.op.map_or_else(|| "=".to_owned(), |op| op.value());
+                let sql = format!("{column} {} ", op.trim());
+                bound_types.push(value_ty);
+                quote! {
+                    fragment.push(#sql);
+                    fragment.push_bind(::std::clone::Clone::clone(value));
+                }
+            }
+        };
+
+        let condition = quote! {
+            let mut fragment = ::sqlx_fragment::Fragment::default();
+            #push
+            where_clause.push_fragment(fragment);
+        };
+        conditions.push(if optional {
+            quote! {
+                if let ::std::option::Option::Some(value) = &self.#member {
+                    #condition
+                }
+            }
+        } else {
+            quote! {
+                {
+                    let value = &self.#member;
+                    #condition
+                }
+            }
+        });
+    }
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!('__args));
+    generics.params.push(parse_quote!(__DB));
+    let where_clause = generics.make_where_clause();
+    where_clause
+        .predicates
+        .push(parse_quote!(__DB: ::sqlx_fragment::__private::sqlx::Database));
+    for ty in &bound_types {
+        where_clause.predicates.push(parse_quote! {
+            #ty: '__args
+                + ::std::marker::Send
+                + ::std::marker::Sync
+                + ::std::clone::Clone
+                + ::std::fmt::Debug
+                + ::sqlx_fragment::__private::sqlx::Encode<'__args, __DB>
+                + ::sqlx_fragment::__private::sqlx::Type<__DB>
+        });
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::sqlx_fragment::Filter<'__args, __DB> for #ident #ty_generics
+        #where_clause
+        {
+            fn push_conditions(
+                &self,
+                where_clause: &mut ::sqlx_fragment::builder2::WhereClause<'_, '__args, __DB>,
+            ) {
+                #(#conditions)*
+            }
+        }
+    })
+}
@@ -0,0 +1,347 @@
+//! Implementation of the `fragment!()` macro.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitStr, Token};
+
+use crate::check;
+
+/// The parsed input of `fragment!()`: a format string followed by optional arguments.
+struct Input {
+    format: LitStr,
+    args: Vec<Arg>,
+}
+
+struct Arg {
+    name: Option<Ident>,
+    expr: Expr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let format = input.parse()?;
+        let mut args = Vec::new();
+
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            let parsed = Punctuated::<Arg, Token![,]>::parse_terminated(input)?;
+            args.extend(parsed);
+        }
+
+        Ok(Input { format, args })
+    }
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+            let name = input.parse()?;
+            input.parse::<Token![=]>()?;
+            Some(name)
+        } else {
+            None
+        };
+
+        Ok(Arg {
+            name,
+            expr: input.parse()?,
+        })
+    }
+}
+
+/// How the value of a placeholder is pushed to the fragment.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HoleKind {
+    /// `{x}`: a bind argument.
+    Bind,
+    /// `{x:frag}`: another fragment.
+    Frag,
+    /// `{x:raw}`: trusted raw SQL.
+    Raw,
+}
+
+/// Which value a placeholder refers to.
+pub(crate) enum HoleArg {
+    /// `{}`: the next positional argument.
+    Next,
+    /// `{0}`: a positional argument.
+    Index(usize),
+    /// `{name}`: a named argument, or a variable captured from the surrounding scope.
+    Name(String),
+}
+
+/// The value a placeholder refers to, once resolved.
+#[derive(PartialEq, Eq)]
+enum Value {
+    /// An argument of the macro, by index.
+    Arg(usize),
+    /// A variable captured from the surrounding scope.
+    Captured(String),
+}
+
+impl Value {
+    fn describe(&self) -> String {
+        match self {
+            Value::Arg(index) => format!("argument {index}"),
+            Value::Captured(name) => format!("`{name}`"),
+        }
+    }
+}
+
+/// A piece of the format string, with placeholders resolved.
+enum Resolved {
+    Sql(String),
+    Hole(Value, HoleKind),
+}
+
+pub(crate) enum Piece {
+    Sql(String),
+    Hole(HoleArg, HoleKind),
+}
+
+/// Split a format string into SQL text and placeholders.
+pub(crate) fn parse_format(format: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut sql = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                sql.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                sql.push('}');
+            }
+            '}' => return Err("unmatched `}` in format string; use `}}` to escape it".into()),
+            '{' => {
+                let mut spec = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => spec.push(c),
+                        None => {
+                            return Err(
+                                "unmatched `{` in format string; use `{{` to escape it".into()
+                            )
+                        }
+                    }
+                }
+
+                if !sql.is_empty() {
+                    pieces.push(Piece::Sql(std::mem::take(&mut sql)));
+                }
+                pieces.push(parse_hole(spec.trim())?);
+            }
+            c => sql.push(c),
+        }
+    }
+
+    if !sql.is_empty() {
+        pieces.push(Piece::Sql(sql));
+    }
+
+    Ok(pieces)
+}
+
+fn parse_hole(spec: &str) -> Result<Piece, String> {
+    let (arg, kind) = match spec.split_once(':') {
+        Some((arg, kind)) => (arg.trim(), kind.trim()),
+        None => (spec, ""),
+    };
+
+    let kind = match kind {
+        "" => HoleKind::Bind,
+        "frag" => HoleKind::Frag,
+        "raw" => HoleKind::Raw,
+        other => {
+            return Err(format!(
+                "unknown placeholder kind `{other}`; expected nothing, `frag` or `raw`"
+            ))
+        }
+    };
+
+    let arg = if arg.is_empty() {
+        HoleArg::Next
+    } else if let Ok(index) = arg.parse() {
+        HoleArg::Index(index)
+    } else if syn::parse_str::<Ident>(arg).is_ok() {
+        HoleArg::Name(arg.to_owned())
+    } else {
+        return Err(format!("invalid argument name `{arg}` in format string"));
+    };
+
+    Ok(Piece::Hole(arg, kind))
+}
+
+pub(crate) fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let Input { format, args } = syn::parse2(input)?;
+    let span = format.span();
+    let pieces = parse_format(&format.value()).map_err(|msg| syn::Error::new(span, msg))?;
+
+    let mut sql = String::new();
+    for piece in &pieces {
+        match piece {
+            Piece::Sql(text) => sql.push_str(text),
+            Piece::Hole(..) => sql.push(check::HOLE),
+        }
+    }
+    check::check_sql(&sql).map_err(|msg| syn::Error::new(span, msg))?;
+
+    let positional = args.iter().take_while(|arg| arg.name.is_none()).count();
+    if let Some(arg) = args[positional..].iter().find(|arg| arg.name.is_none()) {
+        return Err(syn::Error::new_spanned(
+            &arg.expr,
+            "positional arguments cannot follow named arguments",
+        ));
+    }
+
+    let locals: Vec<Ident> = (0..args.len())
+        .map(|i| format_ident!("__arg{}", i, span = Span::mixed_site()))
+        .collect();
+    let fragment = Ident::new("__fragment", Span::mixed_site());
+
+    // Resolve every placeholder to the value it refers to, first.
+    let mut next = 0;
+    let mut resolved = Vec::new();
+    for piece in pieces {
+        let (arg, kind) = match piece {
+            Piece::Sql(sql) => {
+                resolved.push(Resolved::Sql(sql));
+                continue;
+            }
+            Piece::Hole(arg, kind) => (arg, kind),
+        };
+
+        let value = match arg {
+            HoleArg::Next | HoleArg::Index(_) => {
+                let index = match arg {
+                    HoleArg::Index(index) => index,
+                    _ => {
+                        next += 1;
+                        next - 1
+                    }
+                };
+                if index >= positional {
+                    return Err(syn::Error::new(
+                        span,
+                        format!("invalid reference to positional argument {index}"),
+                    ));
+                }
+                Value::Arg(index)
+            }
+            HoleArg::Name(name) => match args
+                .iter()
+                .position(|arg| arg.name.as_ref().is_some_and(|n| *n == name))
+            {
+                Some(index) => Value::Arg(index),
+                None => Value::Captured(name),
+            },
+        };
+        resolved.push(Resolved::Hole(value, kind));
+    }
+
+    // A value used by several placeholders is bound once, and repeated afterwards. Fragments
+    // and raw SQL can't be repeated, as they would be moved twice.
+    let mut uses: Vec<(&Value, HoleKind, usize)> = Vec::new();
+    for resolved in &resolved {
+        let Resolved::Hole(value, kind) = resolved else {
+            continue;
+        };
+        match uses.iter_mut().find(|(used, _, _)| *used == value) {
+            Some((_, used_kind, count)) => {
+                if *used_kind != HoleKind::Bind || *kind != HoleKind::Bind {
+                    return Err(syn::Error::new(
+                        span,
+                        format!(
+                            "{} is used by several placeholders; only bind arguments (`{{}}`) \
+                             can be used more than once",
+                            value.describe()
+                        ),
+                    ));
+                }
+                *count += 1;
+            }
+            None => uses.push((value, *kind, 1)),
+        }
+    }
+    let repeated: Vec<&Value> = uses
+        .iter()
+        .filter(|(_, _, count)| *count > 1)
+        .map(|(value, _, _)| *value)
+        .collect();
+    let indices: Vec<Ident> = (0..repeated.len())
+        .map(|i| format_ident!("__index{}", i, span = Span::mixed_site()))
+        .collect();
+
+    let mut used = vec![false; args.len()];
+    let mut bound = vec![false; repeated.len()];
+    let mut pushes = Vec::new();
+
+    for resolved in &resolved {
+        let (value, kind) = match resolved {
+            Resolved::Sql(sql) => {
+                pushes.push(quote! { #fragment.push(#sql); });
+                continue;
+            }
+            Resolved::Hole(value, kind) => (value, *kind),
+        };
+
+        let tokens = match value {
+            Value::Arg(index) => {
+                used[*index] = true;
+                let local = &locals[*index];
+                quote! { #local }
+            }
+            Value::Captured(name) => {
+                let ident = Ident::new(name, span);
+                quote! { #ident }
+            }
+        };
+
+        if let Some(position) = repeated.iter().position(|repeated| *repeated == value) {
+            let index = &indices[position];
+            if bound[position] {
+                pushes.push(quote_spanned! {span=>
+                    ::sqlx_fragment::__private::push_repeat(&mut #fragment, #index);
+                });
+            } else {
+                bound[position] = true;
+                pushes.push(quote_spanned! {span=>
+                    let #index = #fragment.bind_count();
+                    #fragment.push_bind(#tokens);
+                });
+            }
+            continue;
+        }
+
+        pushes.push(match kind {
+            HoleKind::Bind => quote_spanned! {span=> #fragment.push_bind(#tokens); },
+            HoleKind::Frag => quote_spanned! {span=> #fragment.push_fragment(#tokens); },
+            HoleKind::Raw => {
+                quote_spanned! {span=> #fragment.push(::sqlx_fragment::__private::raw_sql(#tokens)); }
+            }
+        });
+    }
+
+    if let Some(index) = used.iter().position(|used| !used) {
+        return Err(syn::Error::new_spanned(
+            &args[index].expr,
+            "argument never used in format string",
+        ));
+    }
+
+    let exprs = args.iter().map(|arg| &arg.expr);
+
+    Ok(quote! {
+        {
+            #( let #locals = #exprs; )*
+            let mut #fragment = ::sqlx_fragment::Fragment::default();
+            #( #pushes )*
+            #fragment
+        }
+    })
+}
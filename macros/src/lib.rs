@@ -0,0 +1,46 @@
+// Copyright 2024 Olivier FAURE
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Procedural macros for `sqlx-fragment`. See the documentation of the re-exports in
+//! `sqlx_fragment` for details.
+
+use proc_macro::TokenStream;
+use sqlx_fragment_lexer as lexer;
+
+mod check;
+mod filter;
+mod fragment;
+mod named;
+mod sort;
+
+#[proc_macro]
+pub fn fragment(input: TokenStream) -> TokenStream {
+    match fragment::expand(input.into()) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(Filter, attributes(filter))]
+pub fn derive_filter(input: TokenStream) -> TokenStream {
+    match filter::expand(input.into()) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(NamedArguments, attributes(named))]
+pub fn derive_named_arguments(input: TokenStream) -> TokenStream {
+    match named::expand(input.into()) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(SortKey, attributes(sort))]
+pub fn derive_sort_key(input: TokenStream) -> TokenStream {
+    match sort::expand(input.into()) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,124 @@
+//! Implementation of `#[derive(NamedArguments)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, Fields, LitStr};
+
+/// The options of a `#[named(...)]` field attribute.
+#[derive(Default)]
+struct FieldOptions {
+    rename: Option<String>,
+    skip: bool,
+}
+
+fn field_options(field: &syn::Field) -> syn::Result<FieldOptions> {
+    let mut options = FieldOptions::default();
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("named"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                options.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename = \"...\"` or `skip`"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
+pub(crate) fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+    let ident = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "`NamedArguments` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`NamedArguments` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut names = Vec::new();
+    let mut members = Vec::new();
+    let mut types = Vec::new();
+    for field in fields {
+        let options = field_options(field)?;
+        if options.skip {
+            continue;
+        }
+
+        let member = field.ident.as_ref().expect("named field");
+        let name = options
+            .rename
+            .unwrap_or_else(|| member.to_string().trim_start_matches("r#").to_owned());
+        names.push(name);
+        members.push(member);
+        types.push(&field.ty);
+    }
+
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!('__args));
+    generics.params.push(parse_quote!(__DB));
+    let where_clause = generics.make_where_clause();
+    where_clause
+        .predicates
+        .push(parse_quote!(__DB: ::sqlx_fragment::__private::sqlx::Database));
+    for ty in &types {
+        where_clause.predicates.push(parse_quote! {
+            #ty: '__args
+                + ::std::marker::Send
+                + ::std::marker::Sync
+                + ::std::clone::Clone
+                + ::std::fmt::Debug
+                + ::sqlx_fragment::__private::sqlx::Encode<'__args, __DB>
+                + ::sqlx_fragment::__private::sqlx::Type<__DB>
+        });
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::sqlx_fragment::NamedArguments<'__args, __DB> for #ident #ty_generics
+        #where_clause
+        {
+            fn push_named_bind(
+                &self,
+                name: &str,
+                fragment: &mut ::sqlx_fragment::Fragment<'__args, __DB>,
+            ) -> bool {
+                match name {
+                    #(
+                        #names => {
+                            fragment.push_bind(::std::clone::Clone::clone(&self.#members));
+                            true
+                        }
+                    )*
+                    _ => false,
+                }
+            }
+
+            fn names(&self) -> ::std::vec::Vec<&str> {
+                ::std::vec![#(#names),*]
+            }
+        }
+    })
+}
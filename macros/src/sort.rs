@@ -0,0 +1,111 @@
+//! Implementation of `#[derive(SortKey)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr};
+
+/// The options of a `#[sort(...)]` variant attribute.
+#[derive(Default)]
+struct VariantOptions {
+    rename: Option<String>,
+    column: Option<String>,
+}
+
+fn variant_options(variant: &syn::Variant) -> syn::Result<VariantOptions> {
+    let mut options = VariantOptions::default();
+
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("sort"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                options.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if meta.path.is_ident("column") {
+                options.column = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename = \"...\"` or `column = \"...\"`"))
+            }
+        })?;
+    }
+
+    Ok(options)
+}
+
+/// Convert a `CamelCase` identifier to `snake_case`, keeping acronyms together: `HttpStatus`
+/// and `HTTPStatus` both become `http_status`.
+fn snake_case(ident: &str) -> String {
+    let chars: Vec<char> = ident.trim_start_matches("r#").chars().collect();
+    let mut snake = String::with_capacity(chars.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+pub(crate) fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let input: DeriveInput = syn::parse2(input)?;
+    let ident = &input.ident;
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`SortKey` can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut keys = Vec::new();
+    let mut columns = Vec::new();
+    let mut members = Vec::new();
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`SortKey` can only be derived for enums whose variants have no fields",
+            ));
+        }
+
+        let options = variant_options(variant)?;
+        let name = snake_case(&variant.ident.to_string());
+        keys.push(options.rename.unwrap_or_else(|| name.clone()));
+        columns.push(options.column.unwrap_or(name));
+        members.push(&variant.ident);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::sqlx_fragment::SortKey for #ident #ty_generics
+        #where_clause
+        {
+            fn from_sort_key(key: &str) -> ::std::option::Option<Self> {
+                match key {
+                    #(#keys => ::std::option::Option::Some(Self::#members),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn column(&self) -> &'static str {
+                match *self {
+                    #(Self::#members => #columns,)*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,111 @@
+//! Deferred, type-erased bind arguments.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use sqlx::database::Database;
+use sqlx::encode::Encode;
+use sqlx::error::BoxDynError;
+use sqlx::types::Type;
+use sqlx::Arguments;
+
+/// A bind argument whose value is only added to a real [`Database::Arguments`] on demand.
+///
+/// `Database::Arguments` can't be iterated or merged, so values are kept in this form until
+/// the query is built.
+pub(crate) trait DeferredBind<'args, DB: Database>: Send + 'args {
+    /// Add the value to `arguments`.
+    fn bind(
+        self: Box<Self>,
+        arguments: &mut <DB as Database>::Arguments<'args>,
+    ) -> Result<(), BoxDynError>;
+
+    /// The value, for debugging purposes.
+    fn value(&self) -> &dyn Debug;
+}
+
+/// A [`DeferredBind`] holding a value that is moved to the arguments.
+pub(crate) struct Owned<T>(pub T);
+
+impl<'args, DB, T> DeferredBind<'args, DB> for Owned<T>
+where
+    DB: Database,
+    T: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
+{
+    fn bind(
+        self: Box<Self>,
+        arguments: &mut <DB as Database>::Arguments<'args>,
+    ) -> Result<(), BoxDynError> {
+        arguments.add(self.0)
+    }
+
+    fn value(&self) -> &dyn Debug {
+        &self.0
+    }
+}
+
+pub(crate) type BoxedBind<'args, DB> = Box<dyn DeferredBind<'args, DB>>;
+
+/// A bind argument that can be added to any number of [`Database::Arguments`], by cloning it.
+pub(crate) trait SharedBind<'args, DB: Database>: Send + Sync + 'args {
+    /// Add a clone of the value to `arguments`.
+    fn bind_clone(
+        &self,
+        arguments: &mut <DB as Database>::Arguments<'args>,
+    ) -> Result<(), BoxDynError>;
+
+    /// The value, for debugging purposes.
+    fn value(&self) -> &dyn Debug;
+}
+
+impl<'args, DB, T> SharedBind<'args, DB> for T
+where
+    DB: Database,
+    T: 'args + Send + Sync + Clone + Debug + Encode<'args, DB> + Type<DB>,
+{
+    fn bind_clone(
+        &self,
+        arguments: &mut <DB as Database>::Arguments<'args>,
+    ) -> Result<(), BoxDynError> {
+        arguments.add(self.clone())
+    }
+
+    fn value(&self) -> &dyn Debug {
+        self
+    }
+}
+
+/// A reference-counted [`SharedBind`], cheap to clone.
+pub(crate) struct Shared<'args, DB: Database>(Arc<dyn SharedBind<'args, DB>>);
+
+impl<'args, DB: Database> Clone for Shared<'args, DB> {
+    fn clone(&self) -> Self {
+        Shared(Arc::clone(&self.0))
+    }
+}
+
+impl<'args, DB: Database> Shared<'args, DB> {
+    pub fn new<T>(value: T) -> Self
+    where
+        T: 'args + Send + Sync + Clone + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        Shared(Arc::new(value))
+    }
+
+    pub fn boxed(self) -> BoxedBind<'args, DB> {
+        Box::new(self)
+    }
+}
+
+impl<'args, DB: Database> DeferredBind<'args, DB> for Shared<'args, DB> {
+    fn bind(
+        self: Box<Self>,
+        arguments: &mut <DB as Database>::Arguments<'args>,
+    ) -> Result<(), BoxDynError> {
+        self.0.bind_clone(arguments)
+    }
+
+    fn value(&self) -> &dyn Debug {
+        self.0.value()
+    }
+}
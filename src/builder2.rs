@@ -1,26 +1,39 @@
 //! Runtime query-builder API.
 
-use std::fmt::Display;
 use std::fmt::Write;
+use std::fmt::{Debug, Display};
+use std::iter::Peekable;
 use std::marker::PhantomData;
+use std::sync::{Mutex, PoisonError};
 
 use sqlx::database::Database;
 use sqlx::encode::Encode;
+use sqlx::error::BoxDynError;
 use sqlx::query::Query;
 use sqlx::query::QueryAs;
 use sqlx::query::QueryScalar;
 use sqlx::types::Type;
-use sqlx::Either;
 use sqlx::FromRow;
-use sqlx::{Arguments, IntoArguments};
+use sqlx::{Arguments, Execute, IntoArguments};
+
+use crate::arguments::{BoxedBind, Owned, Shared};
+use crate::cond::Cond;
+use crate::debug::{self, DebugSql};
+use crate::dialect::{Dialect, PlaceholderStyle};
+use crate::error::Error;
+use crate::filter::Filter;
+use crate::fragment::{Fragment, PushFragment};
+use crate::lexer::{self, Lexer, TokenKind};
+use crate::named::NamedArguments;
+use crate::tuple::BindTuple;
 
 /// A builder type for constructing queries at runtime.
 ///
 /// See [`.push_values()`][Self::push_values] for an example of building a bulk `INSERT` statement.
 /// Note, however, that with Postgres you can get much better performance by using arrays
-/// and `UNNEST()`. [See our FAQ] for details.
+/// and `UNNEST()`, see [`.push_insert_unnest()`][Self::push_insert_unnest] and [our FAQ].
 ///
-/// [See our FAQ]: https://github.com/launchbadge/sqlx/blob/master/FAQ.md#how-can-i-bind-an-array-to-a-values-clause-how-can-i-do-bulk-inserts
+/// [our FAQ]: https://github.com/launchbadge/sqlx/blob/master/FAQ.md#how-can-i-bind-an-array-to-a-values-clause-how-can-i-do-bulk-inserts
 pub struct QueryBuilder<'args, DB>
 where
     DB: Database,
@@ -28,6 +41,11 @@ where
     query: String,
     init_len: usize,
     arguments: Option<<DB as Database>::Arguments<'args>>,
+    // Bound after `arguments`; kept aside so they can be moved to another builder.
+    binds: Vec<BoxedBind<'args, DB>>,
+    // Holds `query` and the arguments once built, since `sqlx::QueryBuilder::build()` is the
+    // only way to borrow a `Query` from a builder for every backend.
+    built: Option<sqlx::QueryBuilder<'args, DB>>,
 }
 
 impl<'args, DB: Database> Default for QueryBuilder<'args, DB> {
@@ -36,6 +54,8 @@ impl<'args, DB: Database> Default for QueryBuilder<'args, DB> {
             init_len: 0,
             query: String::default(),
             arguments: Some(Default::default()),
+            binds: Vec::new(),
+            built: None,
         }
     }
 }
@@ -57,6 +77,8 @@ where
             init_len: init.len(),
             query: init,
             arguments: Some(Default::default()),
+            binds: Vec::new(),
+            built: None,
         }
     }
 
@@ -75,15 +97,34 @@ where
             init_len: init.len(),
             query: init,
             arguments: Some(arguments.into_arguments()),
+            binds: Vec::new(),
+            built: None,
         }
     }
 
     #[inline]
     fn sanity_check(&self) {
-        assert!(
-            self.arguments.is_some(),
-            "QueryBuilder must be reset before reuse after `.build()`"
-        );
+        if let Err(error) = self.check_not_built() {
+            panic!("{error}");
+        }
+    }
+
+    #[inline]
+    pub(crate) fn check_not_built(&self) -> Result<(), Error> {
+        match self.arguments {
+            Some(_) => Ok(()),
+            None => Err(Error::AlreadyBuilt),
+        }
+    }
+
+    /// Check that the query can have `additional` more bind arguments.
+    fn check_bind_limit(&self, additional: usize) -> Result<(), Error> {
+        let limit = Dialect::of::<DB>().bind_limit();
+        if self.bind_count() + additional > limit {
+            return Err(Error::TooManyArguments { limit });
+        }
+
+        Ok(())
     }
 
     /// Append a SQL fragment to the query.
@@ -92,6 +133,9 @@ where
     /// You can also use `format_args!()` here to push a formatted string without an intermediate
     /// allocation.
     ///
+    /// See [`StrictQueryBuilder`][crate::StrictQueryBuilder] for a builder that only accepts
+    /// string literals and values explicitly marked as trusted.
+    ///
     /// ### Warning: Beware SQL Injection Vulnerabilities and Untrusted Input!
     /// You should *not* use this to insert input directly into the query from an untrusted user as
     /// this can be used by an attacker to extract sensitive data or take over your database.
@@ -121,8 +165,55 @@ where
         self
     }
 
+    /// Append a quoted identifier, such as a table or column name, to the query.
+    ///
+    /// The identifier is quoted for the backend `DB` (`"ident"` for Postgres and SQLite,
+    /// `` `ident` `` for MySQL) and any quote character it contains is escaped, so it is safe to
+    /// use with a name that isn't known until runtime. Note that quoted identifiers are
+    /// case-sensitive in most databases.
+    ///
+    /// ```rust
+    /// # #[cfg(all(feature = "postgres", feature = "mysql"))] {
+    /// use sqlx::{MySql, Postgres};
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM ");
+    /// query_builder.push_ident("my \"table\"");
+    /// assert_eq!(query_builder.sql(), r#"SELECT * FROM "my ""table""""#);
+    ///
+    /// let mut query_builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT * FROM ");
+    /// query_builder.push_ident("my `table`");
+    /// assert_eq!(query_builder.sql(), "SELECT * FROM `my ``table```");
+    /// # }
+    /// ```
+    pub fn push_ident(&mut self, ident: &str) -> &mut Self {
+        self.sanity_check();
+
+        Dialect::of::<DB>().quote_ident(ident, &mut self.query);
+
+        self
+    }
+
+    /// Append a qualified identifier, such as `schema.table.column`, quoting each part.
+    ///
+    /// See [`.push_ident()`][Self::push_ident] for details.
+    pub fn push_qualified_ident<I>(&mut self, parts: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.sanity_check();
+
+        Dialect::of::<DB>().quote_qualified_ident(parts, &mut self.query);
+
+        self
+    }
+
     /// Push a bind argument placeholder (`?` or `$N` for Postgres) and bind a value to it.
     ///
+    /// The value must implement `Debug`, so that it can be shown by
+    /// [`.to_debug_sql()`][Self::to_debug_sql].
+    ///
     /// ### Note: Database-specific Limits
     /// Note that every database has a practical limit on the number of bind parameters
     /// you can add to a single query. This varies by database.
@@ -147,47 +238,281 @@ where
     /// [postgres-limit-issue]: https://github.com/launchbadge/sqlx/issues/671#issuecomment-687043510
     pub fn push_bind<T>(&mut self, value: T) -> &mut Self
     where
-        T: 'args + Encode<'args, DB> + Type<DB>,
+        T: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.push_boxed_bind(Box::new(Owned(value)))
+    }
+
+    /// Push a bind argument placeholder and bind a value to it, or return an error if the builder
+    /// was already built or the bind argument limit of the backend is reached.
+    ///
+    /// The value is only encoded when the query is built, so encoding errors are returned by
+    /// [`.try_build()`][Self::try_build].
+    pub fn try_push_bind<T>(&mut self, value: T) -> Result<&mut Self, Error>
+    where
+        T: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
     {
+        self.check_not_built()?;
+        self.check_bind_limit(1)?;
+
+        Ok(self.push_bind(value))
+    }
+
+    pub(crate) fn push_boxed_bind(&mut self, bind: BoxedBind<'args, DB>) -> &mut Self {
         self.sanity_check();
 
-        let arguments = self
-            .arguments
-            .as_mut()
-            .expect("BUG: Arguments taken already");
-        arguments.add(value).expect("Failed to add argument");
+        self.binds.push(bind);
+        Dialect::of::<DB>()
+            .placeholder_style()
+            .write(self.bind_count(), &mut self.query);
+
+        self
+    }
+
+    /// Append a placeholder for a bind argument that is used a second time, with placeholder
+    /// number `number` if the backend has numbered placeholders.
+    pub(crate) fn push_repeated_bind(&mut self, bind: &Shared<'args, DB>, number: usize) {
+        match Dialect::of::<DB>().placeholder_style() {
+            PlaceholderStyle::Numbered => {
+                self.sanity_check();
+                PlaceholderStyle::Numbered.write(number, &mut self.query);
+            }
+            PlaceholderStyle::Positional => {
+                self.push_boxed_bind(bind.clone().boxed());
+            }
+        }
+    }
+
+    /// Append SQL with named parameters, such as `:name`, taking their values from `arguments`.
+    ///
+    /// See [`Fragment::push_named()`][crate::Fragment::push_named] for details.
+    pub fn push_named<A>(&mut self, sql: impl Display, arguments: &A) -> Result<&mut Self, Error>
+    where
+        A: NamedArguments<'args, DB> + ?Sized,
+    {
+        self.sanity_check();
+
+        let mut fragment = Fragment::default();
+        fragment.push_named(sql, arguments)?;
+
+        Ok(self.push_fragment(fragment))
+    }
+
+    /// Push a parenthesized list of bind arguments, one per element of `tuple`, e.g. `($1, $2)`.
+    ///
+    /// This works for tuples of up to 16 elements; see [`BindTuple`].
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM t WHERE (a, b, c) = ");
+    /// query_builder.push_bind_tuple((1, "two", 3.0));
+    /// assert_eq!(query_builder.sql(), "SELECT * FROM t WHERE (a, b, c) = ($1, $2, $3)");
+    /// # }
+    /// ```
+    pub fn push_bind_tuple(&mut self, tuple: impl BindTuple<'args, DB>) -> &mut Self {
+        let mut separated = self.separated(", ").delimiters("(", ")");
+        tuple.push_binds(&mut separated);
+        separated.finish();
+
+        self
+    }
+
+    /// Append a `column IN (...)` predicate, with one bind argument per value.
+    ///
+    /// If `values` is empty, `FALSE` is pushed instead, as `column IN ()` is a syntax error in
+    /// most databases and nothing is in an empty list anyway.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM food WHERE ");
+    /// query_builder.push_in("name", ["pizza", "chips"]);
+    /// assert_eq!(query_builder.sql(), "SELECT * FROM food WHERE name IN ($1, $2)");
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM food WHERE ");
+    /// query_builder.push_in("name", Vec::<String>::new());
+    /// assert_eq!(query_builder.sql(), "SELECT * FROM food WHERE FALSE");
+    /// # }
+    /// ```
+    pub fn push_in<I>(&mut self, column: impl Display, values: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.push_in_list(column, " IN (", "FALSE", values)
+    }
+
+    /// Append a `column NOT IN (...)` predicate, with one bind argument per value.
+    ///
+    /// If `values` is empty, `TRUE` is pushed instead. See [`.push_in()`][Self::push_in].
+    pub fn push_not_in<I>(&mut self, column: impl Display, values: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.push_in_list(column, " NOT IN (", "TRUE", values)
+    }
+
+    fn push_in_list<I>(
+        &mut self,
+        column: impl Display,
+        operator: &str,
+        if_empty: &str,
+        values: I,
+    ) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        let mut values = values.into_iter().peekable();
+        if values.peek().is_none() {
+            return self.push(if_empty);
+        }
+
+        self.push(column);
+        self.push(operator);
+        for (i, value) in values.enumerate() {
+            if i > 0 {
+                self.push(", ");
+            }
+            self.push_bind(value);
+        }
+        self.push(")")
+    }
+
+    /// Append a `(column, ...) IN ((...), ...)` predicate, with one bind argument per element of
+    /// each row, e.g. for composite keys.
+    ///
+    /// If `rows` is empty, `FALSE` is pushed instead, as with [`.push_in()`][Self::push_in].
+    ///
+    /// ### Panics
+    /// If the number of `columns` doesn't match the number of elements in each row.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// let keys = [(1, "en"), (2, "fr")];
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM pages WHERE ");
+    /// query_builder.push_in_tuples(["id", "lang"], keys);
+    /// assert_eq!(
+    ///     query_builder.sql(),
+    ///     "SELECT * FROM pages WHERE (id, lang) IN (($1, $2), ($3, $4))"
+    /// );
+    /// # }
+    /// ```
+    pub fn push_in_tuples<C, I>(&mut self, columns: C, rows: I) -> &mut Self
+    where
+        C: IntoIterator,
+        C::Item: Display,
+        I: IntoIterator,
+        I::Item: BindTuple<'args, DB>,
+    {
+        let mut rows = rows.into_iter().peekable();
+        if rows.peek().is_none() {
+            return self.push("FALSE");
+        }
+
+        let mut separated = self.separated(", ").delimiters("(", ")");
+        for column in columns {
+            separated.push(column);
+        }
+        let count = separated.finish();
+        assert_eq!(
+            count,
+            <I::Item as BindTuple<'args, DB>>::LEN,
+            "`push_in_tuples()` was given {count} columns for rows of {} values",
+            <I::Item as BindTuple<'args, DB>>::LEN
+        );
 
-        arguments
-            .format_placeholder(&mut self.query)
-            .expect("error in format_placeholder");
+        self.push(" IN ");
+        let mut separated = self.separated(", ").delimiters("(", ")");
+        for row in rows {
+            let mut row_builder = separated.push_separated(", ").delimiters("(", ")");
+            row.push_binds(&mut row_builder);
+            row_builder.finish();
+        }
+        separated.finish();
 
         self
     }
 
-    pub fn push_fragment(&mut self, fragment: QueryBuilder<'args, DB>) -> &mut Self {
+    /// Append a [`Fragment`][crate::Fragment] or the contents of another `QueryBuilder` to the
+    /// query, along with its bind arguments.
+    ///
+    /// For backends with numbered placeholders (`$N` for Postgres), the placeholders written by
+    /// the fragment are numbered to follow the arguments already bound to this builder.
+    /// String literals, quoted identifiers, comments and dollar-quoted bodies are left untouched.
+    ///
+    /// ### Panics
+    /// The arguments given to [`with_arguments()`][Self::with_arguments] can't be iterated, so
+    /// a `QueryBuilder` constructed that way can only be pushed to a builder that has no bind
+    /// arguments yet. This method panics otherwise; use
+    /// [`.try_push_fragment()`][Self::try_push_fragment] to get an error instead.
+    pub fn push_fragment(&mut self, fragment: impl PushFragment<'args, DB>) -> &mut Self {
         self.sanity_check();
 
+        fragment.push_to(self);
+
+        self
+    }
+
+    /// Append a [`Fragment`][crate::Fragment] or the contents of another `QueryBuilder` to the
+    /// query, or return an error and leave the query unchanged if it can't be pushed.
+    ///
+    /// Unlike [`.push_fragment()`][Self::push_fragment], this checks that the bind argument
+    /// limit of the backend isn't exceeded.
+    pub fn try_push_fragment(
+        &mut self,
+        fragment: impl PushFragment<'args, DB>,
+    ) -> Result<&mut Self, Error> {
+        self.check_not_built()?;
+
+        let checkpoint = self.checkpoint();
+        fragment.try_push_to(self)?;
+
+        if let Err(error) = self.check_bind_limit(0) {
+            self.rollback_to(checkpoint);
+            return Err(error);
+        }
+
+        Ok(self)
+    }
+
+    /// Append a boolean condition built with [`Cond`].
+    ///
+    /// An empty fragment is pushed as `TRUE`, and a group without members as `TRUE` for
+    /// [`Cond::and()`] or `FALSE` for [`Cond::or()`]; see [`Cond`] for details.
+    pub fn push_condition(&mut self, condition: Cond<'args, DB>) -> &mut Self {
+        self.push_fragment(condition.into_fragment())
+    }
+
+    /// The number of bind arguments pushed so far.
+    pub(crate) fn bind_count(&self) -> usize {
         let arguments = self
             .arguments
-            .as_mut()
-            .expect("BUG: Arguments taken already");
-        let fragment_arguments = fragment
-            .arguments
-            .take()
+            .as_ref()
             .expect("BUG: Arguments taken already");
+        arguments.len() + self.binds.len()
+    }
 
-        self.query.push_str(&fragment.query);
+    /// Take the bind arguments, adding every deferred bind to them.
+    fn take_arguments(&mut self) -> Result<<DB as Database>::Arguments<'args>, BoxDynError> {
+        let mut arguments = self.arguments.take().expect("BUG: Arguments taken already");
 
-        arguments.reserve(fragment_arguments.len(), 0);
-        for argument in fragment.arguments {
-            arguments.add(argument).expect("Failed to add argument");
+        arguments.reserve(self.binds.len(), 0);
+        for bind in self.binds.drain(..) {
+            bind.bind(&mut arguments)?;
         }
 
-        arguments
-            .format_placeholder(&mut self.query)
-            .expect("error in format_placeholder");
-
-        self
+        Ok(arguments)
     }
 
     /// Start a list separated by `separator`.
@@ -197,6 +522,14 @@ where
     /// before their normal behavior. [`.push_unseparated()`][Separated::push_unseparated] and [`.push_bind_unseparated()`][Separated::push_bind_unseparated] are also
     /// provided to push a SQL fragment without the separator.
     ///
+    /// The list can be wrapped in delimiters with [`.delimiters()`][Separated::delimiters], or
+    /// replaced by a fallback if it is empty with [`.if_empty()`][Separated::if_empty], in which
+    /// case [`.finish()`][Separated::finish] must be called at the end of the list. Lists can be
+    /// nested with [`.push_separated()`][Separated::push_separated].
+    ///
+    /// To build an `IN (...)` list, prefer [`.push_in()`][Self::push_in], which handles the empty
+    /// case.
+    ///
     /// ```rust
     /// # #[cfg(feature = "mysql")] {
     /// use sqlx::{Execute, MySql, QueryBuilder};
@@ -217,7 +550,6 @@ where
     /// assert!(sql.ends_with("in (?, ?) "));
     /// # }
     /// ```
-
     pub fn separated<'qb, Sep>(&'qb mut self, separator: Sep) -> Separated<'qb, 'args, DB, Sep>
     where
         'args: 'qb,
@@ -228,106 +560,739 @@ where
         Separated {
             query_builder: self,
             separator,
-            push_separator: false,
+            count: 0,
+            open: None,
+            close: None,
+            if_empty: None,
         }
     }
 
-    fn into_sqlx_query_builder(self) -> sqlx::QueryBuilder<'args, DB> {
-        let arguments = ArgumentsWrapper(self.arguments.unwrap());
-        sqlx::QueryBuilder::with_arguments(self.query, arguments)
-    }
-
-    /// Reset this `QueryBuilder` back to its initial state.
+    /// Push a `VALUES` clause where each item in `rows` becomes a parenthesized row.
     ///
-    /// The query is truncated to the initial fragment provided to [`new()`][Self::new] and
-    /// the bind arguments are reset.
-    pub fn reset(&mut self) -> &mut Self {
-        self.query.truncate(self.init_len);
-        self.arguments = Some(Default::default());
+    /// For each item, `push_row` is called with a [`Separated`] whose separator is `", "`, and
+    /// should push the columns of the row with [`.push_bind()`][Separated::push_bind] or
+    /// [`.push()`][Separated::push]. A `VALUES` clause with no row is not valid SQL, so `rows`
+    /// should not be empty.
+    ///
+    /// Every row adds its columns to the bind arguments of the query, which are limited; see
+    /// [`.push_bind()`][Self::push_bind]. To insert an arbitrary number of rows, use
+    /// [`QueryBuilder::chunked_values()`] instead.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// struct User {
+    ///     id: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// let users = (0..3).map(|i| User { id: i, name: format!("user{i}") });
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("INSERT INTO users (id, name) ");
+    /// query_builder.push_values(users, |mut row, user| {
+    ///     row.push_bind(user.id).push_bind(user.name);
+    /// });
+    ///
+    /// assert_eq!(
+    ///     query_builder.sql(),
+    ///     "INSERT INTO users (id, name) VALUES ($1, $2), ($3, $4), ($5, $6)"
+    /// );
+    /// # }
+    /// ```
+    pub fn push_values<I, F>(&mut self, rows: I, mut push_row: F) -> &mut Self
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, 'args, DB, &'static str>, I::Item),
+    {
+        self.sanity_check();
+
+        self.push("VALUES ");
+        let mut separated = self.separated(", ");
+        for row in rows {
+            separated.push_row(row, &mut push_row);
+        }
+
+        let count = separated.finish();
+        debug_assert!(
+            count > 0,
+            "No value being pushed. QueryBuilder may not build correct sql query!"
+        );
 
         self
     }
 
-    /// Get the current build SQL; **note**: may not be syntactically correct.
-    pub fn sql(&self) -> &str {
-        &self.query
+    /// Split a bulk `INSERT` into as many queries as needed to stay within the bind argument
+    /// limit of the backend.
+    ///
+    /// Each query yielded by the returned iterator starts with `init`, followed by a `VALUES`
+    /// clause built as with [`.push_values()`][Self::push_values], with as many rows as fit. A
+    /// suffix such as `ON CONFLICT DO NOTHING` can be pushed to each query before it is built.
+    /// No query is yielded if `rows` is empty.
+    ///
+    /// The limit defaults to the one listed in [`.push_bind()`][Self::push_bind], and can be
+    /// changed with [`ChunkedValues::bind_limit()`], e.g. for SQLite prior to 3.32.0 or if the
+    /// query binds other arguments. Every row is expected to bind the same number of arguments.
+    ///
+    /// ### Panics
+    /// When the query for a chunk is built, if a row on its own exceeds the limit, or if a row
+    /// binds more arguments than the first row of the chunk did and doesn't fit.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// let rows = vec![(1, "a"), (2, "b"), (3, "c")];
+    ///
+    /// let queries: Vec<String> = QueryBuilder::<Postgres>::chunked_values(
+    ///     "INSERT INTO t (id, name) ",
+    ///     rows,
+    ///     |mut row, (id, name)| {
+    ///         row.push_bind(id).push_bind(name);
+    ///     },
+    /// )
+    /// .bind_limit(4)
+    /// .map(|mut query_builder| {
+    ///     query_builder.push(" ON CONFLICT DO NOTHING");
+    ///     query_builder.into_sql()
+    /// })
+    /// .collect();
+    ///
+    /// assert_eq!(
+    ///     queries,
+    ///     [
+    ///         "INSERT INTO t (id, name) VALUES ($1, $2), ($3, $4) ON CONFLICT DO NOTHING",
+    ///         "INSERT INTO t (id, name) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    pub fn chunked_values<I, F>(
+        init: impl Display,
+        rows: I,
+        push_row: F,
+    ) -> ChunkedValues<'args, DB, I::IntoIter, F>
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, 'args, DB, &'static str>, I::Item),
+    {
+        ChunkedValues {
+            init: init.to_string(),
+            rows: rows.into_iter().peekable(),
+            push_row,
+            bind_limit: Dialect::of::<DB>().bind_limit(),
+            _db: PhantomData,
+        }
     }
 
-    /// Deconstruct this `QueryBuilder`, returning the built SQL. May not be syntactically correct.
-    pub fn into_sql(self) -> String {
-        self.query
-    }
-}
+    /// Start a `WHERE` clause whose conditions are joined with `AND`.
+    ///
+    /// ` WHERE ` is pushed before the first condition, and ` AND ` between the following ones.
+    /// If no condition is pushed, nothing is written at all, so there is no need for a
+    /// `WHERE 1=1` placeholder. Empty fragments and conditions are skipped.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    /// use sqlx_fragment::fragment;
+    ///
+    /// let min_age: Option<i32> = Some(18);
+    /// let name: Option<&str> = None;
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users");
+    /// let mut where_clause = query_builder.where_clause();
+    /// if let Some(min_age) = min_age {
+    ///     where_clause.push_fragment(fragment!("age >= {min_age}"));
+    /// }
+    /// if let Some(name) = name {
+    ///     where_clause.push_fragment(fragment!("name = {name}"));
+    /// }
+    /// where_clause.push("deleted_at IS NULL");
+    ///
+    /// assert_eq!(
+    ///     query_builder.sql(),
+    ///     "SELECT * FROM users WHERE age >= $1 AND deleted_at IS NULL"
+    /// );
+    /// # }
+    /// ```
+    pub fn where_clause<'qb>(&'qb mut self) -> WhereClause<'qb, 'args, DB>
+    where
+        'args: 'qb,
+    {
+        self.sanity_check();
 
-/// A wrapper around `QueryBuilder` for creating comma(or other token)-separated lists.
-///
-/// See [`QueryBuilder::separated()`] for details.
-#[allow(explicit_outlives_requirements)]
-pub struct Separated<'qb, 'args: 'qb, DB, Sep>
-where
-    DB: Database,
-{
-    query_builder: &'qb mut QueryBuilder<'args, DB>,
-    separator: Sep,
-    push_separator: bool,
-}
+        WhereClause {
+            query_builder: self,
+            empty: true,
+        }
+    }
 
-impl<'qb, 'args: 'qb, DB, Sep> Separated<'qb, 'args, DB, Sep>
-where
-    DB: Database,
-    Sep: Display,
-{
-    /// Push the separator if applicable, and then the given SQL fragment.
+    /// Produce an executable query from this builder.
     ///
-    /// See [`QueryBuilder::push()`] for details.
-    pub fn push(&mut self, sql: impl Display) -> &mut Self {
-        if self.push_separator {
-            self.query_builder
-                .push(format_args!("{}{}", self.separator, sql));
-        } else {
-            self.query_builder.push(sql);
-            self.push_separator = true;
-        }
+    /// ### Note: Query is not Checked
+    /// It is your responsibility to ensure that you produce a syntactically correct query here,
+    /// this API has no way to check it for you.
+    ///
+    /// ### Note: Reuse
+    /// You can reuse this builder afterwards to amortize the allocation overhead of the query
+    /// string, however you must call [`.reset()`][Self::reset] first, which returns `Self`
+    /// to the state it was in immediately after [`new()`][Self::new].
+    ///
+    /// Calling any other method but `.reset()` after `.build()` will panic for sanity reasons.
+    ///
+    /// ### Panics
+    /// If a bind argument can't be encoded. See [`.try_build()`][Self::try_build] for a
+    /// fallible version.
+    pub fn build(&mut self) -> Query<'_, DB, <DB as Database>::Arguments<'args>> {
+        self.sanity_check();
 
-        self
+        match self.finish() {
+            Ok(built) => built.build(),
+            Err(error) => panic!("{error}"),
+        }
     }
 
-    /// Push a SQL fragment without a separator.
+    /// Produce an executable query from this builder.
     ///
-    /// Simply calls [`QueryBuilder::push()`] directly.
-    pub fn push_unseparated(&mut self, sql: impl Display) -> &mut Self {
-        self.query_builder.push(sql);
-        self
+    /// ### Note: Query is not Checked
+    /// It is your responsibility to ensure that you produce a syntactically correct query here,
+    /// this API has no way to check it for you.
+    ///
+    /// ### Note: Reuse
+    /// You can reuse this builder afterwards to amortize the allocation overhead of the query
+    /// string, however you must call [`.reset()`][Self::reset] first, which returns `Self`
+    /// to the state it was in immediately after [`new()`][Self::new].
+    ///
+    /// Calling any other method but `.reset()` after `.build()` will panic for sanity reasons.
+    pub fn build_query_as<'q, T: FromRow<'q, DB::Row>>(
+        &'q mut self,
+    ) -> QueryAs<'q, DB, T, <DB as Database>::Arguments<'args>> {
+        self.sanity_check();
+
+        match self.finish() {
+            Ok(built) => built.build_query_as(),
+            Err(error) => panic!("{error}"),
+        }
     }
 
-    /// Push the separator if applicable, then append a bind argument.
+    /// Produce an executable query from this builder.
     ///
-    /// See [`QueryBuilder::push_bind()`] for details.
-    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    /// ### Note: Query is not Checked
+    /// It is your responsibility to ensure that you produce a syntactically correct query here,
+    /// this API has no way to check it for you.
+    ///
+    /// ### Note: Reuse
+    /// You can reuse this builder afterwards to amortize the allocation overhead of the query
+    /// string, however you must call [`.reset()`][Self::reset] first, which returns `Self`
+    /// to the state it was in immediately after [`new()`][Self::new].
+    ///
+    /// Calling any other method but `.reset()` after `.build()` will panic for sanity reasons.
+    pub fn build_query_scalar<'q, T>(
+        &'q mut self,
+    ) -> QueryScalar<'q, DB, T, <DB as Database>::Arguments<'args>>
     where
-        T: 'args + Encode<'args, DB> + Type<DB>,
+        DB: Database,
+        (T,): for<'r> FromRow<'r, DB::Row>,
     {
-        if self.push_separator {
-            self.query_builder.push(&self.separator);
+        self.sanity_check();
+
+        match self.finish() {
+            Ok(built) => built.build_query_scalar(),
+            Err(error) => panic!("{error}"),
         }
+    }
 
-        self.query_builder.push_bind(value);
-        self.push_separator = true;
+    /// Produce an executable query from this builder, or return an error if it can't be built.
+    ///
+    /// This checks that the number of placeholders in the SQL (`?`, or the highest `$N` for
+    /// Postgres) matches the number of bind arguments, that the bind argument limit of the
+    /// backend isn't exceeded, and encodes the bind arguments.
+    ///
+    /// ### Note: Reuse
+    /// As with `sqlx::QueryBuilder`, you must call [`.reset()`][Self::reset] before reusing this
+    /// builder, including after an [`Error::Encode`], since the arguments are consumed by then.
+    pub fn try_build(
+        &mut self,
+    ) -> Result<Query<'_, DB, <DB as Database>::Arguments<'args>>, Error> {
+        self.check_not_built()?;
+        self.check_bind_limit(0)?;
 
-        self
+        let placeholders = self.placeholder_count();
+        let arguments = self.bind_count();
+        if placeholders != arguments {
+            return Err(Error::PlaceholderMismatch {
+                placeholders,
+                arguments,
+            });
+        }
+
+        Ok(self.finish()?.build())
     }
 
-    /// Push a bind argument placeholder (`?` or `$N` for Postgres) and bind a value to it
-    /// without a separator.
-    ///
-    /// Simply calls [`QueryBuilder::push_bind()`] directly.
+    /// Move the query and its arguments to a `sqlx::QueryBuilder`, to build the query from.
+    fn finish(&mut self) -> Result<&mut sqlx::QueryBuilder<'args, DB>, Error> {
+        let arguments = ArgumentsWrapper(self.take_arguments().map_err(Error::Encode)?);
+        let query = std::mem::take(&mut self.query);
+
+        Ok(self
+            .built
+            .insert(sqlx::QueryBuilder::with_arguments(query, arguments)))
+    }
+
+    /// The number of `?` placeholders in the query, or the highest `$N` for Postgres.
+    fn placeholder_count(&self) -> usize {
+        let dialect = Dialect::of::<DB>();
+        let style = dialect.placeholder_style();
+        let mut count = 0;
+        for token in Lexer::new(&self.query, dialect.syntax()) {
+            match (token.kind, style) {
+                (TokenKind::Numbered(number), PlaceholderStyle::Numbered) => {
+                    count = count.max(number);
+                }
+                (TokenKind::Question, PlaceholderStyle::Positional) => count += 1,
+                _ => {}
+            }
+        }
+        count
+    }
+
+    /// Produce an owned query from this builder, that can be executed directly.
+    ///
+    /// Unlike [`.build()`][Self::build], the result doesn't borrow the builder, so it can be
+    /// returned from a function or stored. A reference to it implements [`Execute`], and can be
+    /// passed to any executor:
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "postgres")]
+    /// # async fn example(pool: &sqlx::PgPool) -> Result<(), sqlx::Error> {
+    /// use sqlx::{Executor, Postgres};
+    /// use sqlx_fragment::builder2::{BuiltQuery, QueryBuilder};
+    ///
+    /// fn users_query(min_age: i32) -> BuiltQuery<'static, Postgres> {
+    ///     let mut query_builder = QueryBuilder::new("SELECT * FROM users WHERE age >= ");
+    ///     query_builder.push_bind(min_age);
+    ///     query_builder.into_query()
+    /// }
+    ///
+    /// let users = pool.fetch_all(&users_query(18)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Bind arguments are encoded by this method, and any encoding error is returned when the
+    /// query is executed. Like a built `sqlx::Query`, the query can only be executed once; later
+    /// attempts return an error.
+    pub fn into_query(mut self) -> BuiltQuery<'args, DB> {
+        self.sanity_check();
+
+        let arguments = self.take_arguments();
+        BuiltQuery {
+            sql: self.query,
+            arguments: Mutex::new(Some(arguments)),
+            persistent: true,
+        }
+    }
+
+    fn try_into_sqlx_query_builder(mut self) -> Result<sqlx::QueryBuilder<'args, DB>, Error> {
+        self.check_not_built()?;
+
+        let arguments = self.take_arguments().map_err(Error::Encode)?;
+        let arguments = ArgumentsWrapper(arguments);
+        Ok(sqlx::QueryBuilder::with_arguments(self.query, arguments))
+    }
+
+    /// Record the current state of the query, to come back to it later with
+    /// [`.rollback_to()`][Self::rollback_to].
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users u");
+    ///
+    /// let checkpoint = query_builder.checkpoint();
+    /// query_builder.push(" JOIN teams t ON t.id = u.team_id WHERE t.name = ");
+    /// query_builder.push_bind("admins");
+    ///
+    /// // The join turns out to be unnecessary.
+    /// query_builder.rollback_to(checkpoint);
+    /// query_builder.push(" WHERE u.age > ").push_bind(18);
+    ///
+    /// assert_eq!(query_builder.sql(), "SELECT * FROM users u WHERE u.age > $1");
+    /// # }
+    /// ```
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.sanity_check();
+
+        Checkpoint {
+            query_len: self.query.len(),
+            binds_len: self.binds.len(),
+            arguments_len: self.bind_count() - self.binds.len(),
+        }
+    }
+
+    /// Truncate the query and its bind arguments back to the state recorded by
+    /// [`.checkpoint()`][Self::checkpoint].
+    ///
+    /// A checkpoint can be rolled back to several times, but only while the query is at least
+    /// as long as when it was taken: after a [`.reset()`][Self::reset], or a rollback to an
+    /// earlier checkpoint, it is no longer valid.
+    ///
+    /// ### Panics
+    /// If the checkpoint is no longer valid, or if the builder was built and not reset.
+    pub fn rollback_to(&mut self, checkpoint: Checkpoint) -> &mut Self {
+        self.sanity_check();
+
+        assert!(
+            checkpoint.query_len <= self.query.len() && checkpoint.binds_len <= self.binds.len(),
+            "checkpoint is no longer valid for this `QueryBuilder`"
+        );
+
+        self.query.truncate(checkpoint.query_len);
+        self.binds.truncate(checkpoint.binds_len);
+        if self.bind_count() - self.binds.len() != checkpoint.arguments_len {
+            // Drop the arguments of a `with_arguments()` builder merged after the checkpoint.
+            self.arguments = Some(Default::default());
+        }
+
+        self
+    }
+
+    /// Reset this `QueryBuilder` back to its initial state.
+    ///
+    /// The query is truncated to the initial fragment provided to [`new()`][Self::new] and
+    /// the bind arguments are reset.
+    pub fn reset(&mut self) -> &mut Self {
+        if let Some(built) = self.built.take() {
+            self.query = built.into_sql();
+        }
+        self.query.truncate(self.init_len);
+        self.arguments = Some(Default::default());
+        self.binds.clear();
+
+        self
+    }
+
+    /// Get the current build SQL; **note**: may not be syntactically correct.
+    pub fn sql(&self) -> &str {
+        match &self.built {
+            Some(built) => built.sql(),
+            None => &self.query,
+        }
+    }
+
+    /// Get the current build SQL with every bind argument inlined as a literal, for logging and
+    /// debugging.
+    ///
+    /// Values are rendered from their `Debug` representation: `None` becomes `NULL`, numbers and
+    /// booleans are written as-is, and strings and anything else become string literals,
+    /// escaped for the backend. Arguments given to [`with_arguments()`][Self::with_arguments]
+    /// can't be rendered and are left as placeholders, and so are all bind arguments once the
+    /// query is [built][Self::build], since they are moved into the query then.
+    ///
+    /// ### Warning: Not Safe to Execute
+    /// The result is only meant to be read by humans. Some values can't be faithfully written as
+    /// literals, and inlining values defeats the protection that bind arguments offer against
+    /// SQL injection. The returned [`DebugSql`] can be displayed but not executed.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users WHERE name = ");
+    /// query_builder
+    ///     .push_bind("O'Brien")
+    ///     .push(" AND age > ")
+    ///     .push_bind(18)
+    ///     .push(" AND deleted_at IS ")
+    ///     .push_bind(None::<i64>);
+    ///
+    /// assert_eq!(
+    ///     query_builder.to_debug_sql().to_string(),
+    ///     "SELECT * FROM users WHERE name = 'O''Brien' AND age > 18 AND deleted_at IS NULL"
+    /// );
+    /// # }
+    /// ```
+    pub fn to_debug_sql(&self) -> DebugSql {
+        if let Some(built) = &self.built {
+            return DebugSql(built.sql().to_owned());
+        }
+
+        let dialect = Dialect::of::<DB>();
+        let style = dialect.placeholder_style();
+        // Arguments given to `with_arguments()` come first, and can't be rendered.
+        let base = self.bind_count() - self.binds.len();
+
+        let mut sql = String::with_capacity(self.query.len());
+        let mut position = 0;
+        for token in Lexer::new(&self.query, dialect.syntax()) {
+            let number = match (token.kind, style) {
+                (TokenKind::Numbered(number), PlaceholderStyle::Numbered) => number,
+                (TokenKind::Question, PlaceholderStyle::Positional) => {
+                    position += 1;
+                    position
+                }
+                _ => 0,
+            };
+
+            let bind = number
+                .checked_sub(base + 1)
+                .and_then(|index| self.binds.get(index));
+            match bind {
+                Some(bind) => debug::write_literal(bind.value(), dialect, &mut sql),
+                None => sql.push_str(token.text),
+            }
+        }
+
+        DebugSql(sql)
+    }
+
+    /// Deconstruct this `QueryBuilder`, returning the built SQL. May not be syntactically correct.
+    pub fn into_sql(self) -> String {
+        match self.built {
+            Some(built) => built.into_sql(),
+            None => self.query,
+        }
+    }
+}
+
+impl<'args, DB: Database> PushFragment<'args, DB> for QueryBuilder<'args, DB> {
+    fn try_push_to(mut self, query_builder: &mut QueryBuilder<'args, DB>) -> Result<(), Error> {
+        self.check_not_built()?;
+        query_builder.check_not_built()?;
+
+        let offset = query_builder.bind_count();
+        let arguments = self.arguments.take().expect("BUG: Arguments taken already");
+        if arguments.len() > 0 && offset > 0 {
+            return Err(Error::ArgumentsMerge);
+        }
+
+        if Dialect::of::<DB>().placeholder_style() == PlaceholderStyle::Numbered {
+            lexer::renumber_placeholders(&self.query, offset, &mut query_builder.query);
+        } else {
+            query_builder.query.push_str(&self.query);
+        }
+
+        if arguments.len() > 0 {
+            query_builder.arguments = Some(arguments);
+        }
+        query_builder.binds.append(&mut self.binds);
+
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.sql().is_empty()
+    }
+}
+
+/// The state of a `QueryBuilder`, returned by [`QueryBuilder::checkpoint()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    query_len: usize,
+    binds_len: usize,
+    // Number of arguments given to `with_arguments()`.
+    arguments_len: usize,
+}
+
+/// A wrapper around `QueryBuilder` for creating comma(or other token)-separated lists.
+///
+/// See [`QueryBuilder::separated()`] for details.
+#[allow(explicit_outlives_requirements)]
+pub struct Separated<'qb, 'args: 'qb, DB, Sep>
+where
+    DB: Database,
+{
+    query_builder: &'qb mut QueryBuilder<'args, DB>,
+    separator: Sep,
+    // Number of items pushed so far.
+    count: usize,
+    open: Option<String>,
+    close: Option<String>,
+    if_empty: Option<String>,
+}
+
+impl<'qb, 'args: 'qb, DB, Sep> Separated<'qb, 'args, DB, Sep>
+where
+    DB: Database,
+    Sep: Display,
+{
+    /// Push `open` before the first item and `close` after the last one, e.g. `(` and `)`.
+    ///
+    /// Nothing is pushed if the list is empty. `close` is pushed by
+    /// [`.finish()`][Self::finish], which must then be called.
+    #[must_use = "call `.finish()` at the end of the list to push the closing delimiter"]
+    pub fn delimiters(mut self, open: impl Display, close: impl Display) -> Self {
+        self.open = Some(open.to_string());
+        self.close = Some(close.to_string());
+        self
+    }
+
+    /// Push `fallback` instead of the list if it is empty, e.g. `NULL`.
+    ///
+    /// `fallback` is pushed by [`.finish()`][Self::finish], which must then be called.
+    #[must_use = "call `.finish()` at the end of the list to push the fallback"]
+    pub fn if_empty(mut self, fallback: impl Display) -> Self {
+        self.if_empty = Some(fallback.to_string());
+        self
+    }
+
+    /// Push the opening delimiter before the first item, or the separator before the others.
+    fn push_separator(&mut self) {
+        if self.count > 0 {
+            self.query_builder.push(&self.separator);
+        } else if let Some(open) = &self.open {
+            self.query_builder.push(open);
+        }
+
+        self.count += 1;
+    }
+
+    /// Push the separator if applicable, and then the given SQL fragment.
+    ///
+    /// See [`QueryBuilder::push()`] for details.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        self.push_separator();
+        self.query_builder.push(sql);
+
+        self
+    }
+
+    /// Push a SQL fragment without a separator.
+    ///
+    /// Simply calls [`QueryBuilder::push()`] directly.
+    pub fn push_unseparated(&mut self, sql: impl Display) -> &mut Self {
+        self.query_builder.push(sql);
+        self
+    }
+
+    /// Push the separator if applicable, then append a bind argument.
+    ///
+    /// See [`QueryBuilder::push_bind()`] for details.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.push_separator();
+        self.query_builder.push_bind(value);
+
+        self
+    }
+
+    /// Push the separator if applicable, then append a [`Fragment`][crate::Fragment] or the
+    /// contents of another `QueryBuilder`.
+    ///
+    /// See [`QueryBuilder::push_fragment()`] for details.
+    pub fn push_fragment(&mut self, fragment: impl PushFragment<'args, DB>) -> &mut Self {
+        self.push_separator();
+        self.query_builder.push_fragment(fragment);
+
+        self
+    }
+
+    /// Push the separator if applicable, then start a nested list separated by `separator`, as a
+    /// single item of this list.
+    ///
+    /// The nested list borrows this one until it is dropped or [finished][Separated::finish],
+    /// and has its own [delimiters][Separated::delimiters] and
+    /// [empty fallback][Separated::if_empty], e.g. for row constructors:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// let pairs = [(1, 2), (3, 4)];
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM t WHERE (x, y) IN ");
+    /// let mut rows = query_builder.separated(", ").delimiters("(", ")");
+    /// for (x, y) in pairs {
+    ///     let mut row = rows.push_separated(", ").delimiters("(", ")");
+    ///     row.push_bind(x).push_bind(y);
+    ///     row.finish();
+    /// }
+    /// rows.finish();
+    ///
+    /// assert_eq!(
+    ///     query_builder.sql(),
+    ///     "SELECT * FROM t WHERE (x, y) IN (($1, $2), ($3, $4))"
+    /// );
+    /// # }
+    /// ```
+    pub fn push_separated<'sub, Sep2>(
+        &'sub mut self,
+        separator: Sep2,
+    ) -> Separated<'sub, 'args, DB, Sep2>
+    where
+        Sep2: Display,
+    {
+        self.push_separator();
+        self.query_builder.separated(separator)
+    }
+
+    /// Push the separator if applicable, then `row` as a parenthesized list of columns.
+    fn push_row<T, F>(&mut self, row: T, push_row: &mut F)
+    where
+        F: FnMut(Separated<'_, 'args, DB, &'static str>, T),
+    {
+        self.push("(");
+        push_row(self.query_builder.separated(", "), row);
+        self.push_unseparated(")");
+    }
+
+    /// Push a bind argument placeholder (`?` or `$N` for Postgres) and bind a value to it
+    /// without a separator.
+    ///
+    /// Simply calls [`QueryBuilder::push_bind()`] directly.
     pub fn push_bind_unseparated<T>(&mut self, value: T) -> &mut Self
     where
-        T: 'args + Encode<'args, DB> + Type<DB>,
+        T: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
     {
         self.query_builder.push_bind(value);
         self
     }
+
+    /// End the list, pushing the closing delimiter or the fallback for an empty list, if any,
+    /// and return the number of items that were pushed.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    /// use sqlx_fragment::fragment;
+    ///
+    /// let tags: Vec<&str> = vec![];
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM posts WHERE ");
+    /// let mut conditions = query_builder
+    ///     .separated(" OR ")
+    ///     .delimiters("(", ")")
+    ///     .if_empty("TRUE");
+    /// for tag in tags {
+    ///     conditions.push_fragment(fragment!("{tag} = ANY(tags)"));
+    /// }
+    /// assert_eq!(conditions.finish(), 0);
+    ///
+    /// assert_eq!(query_builder.sql(), "SELECT * FROM posts WHERE TRUE");
+    /// # }
+    /// ```
+    pub fn finish(self) -> usize {
+        let end = if self.count > 0 {
+            &self.close
+        } else {
+            &self.if_empty
+        };
+        if let Some(end) = end {
+            self.query_builder.push(end);
+        }
+
+        self.count
+    }
 }
 
 pub struct ArgumentsWrapper<'q, DB: Database>(pub <DB as Database>::Arguments<'q>);
@@ -337,3 +1302,564 @@ impl<'q, DB: Database> IntoArguments<'q, DB> for ArgumentsWrapper<'q, DB> {
         self.0
     }
 }
+
+/// An owned, executable query, returned by [`QueryBuilder::into_query()`].
+///
+/// [`Execute`] is implemented for `&BuiltQuery`, since executors borrow the SQL for as long as
+/// the query runs.
+pub struct BuiltQuery<'args, DB: Database> {
+    sql: String,
+    // Taken by `Execute::take_arguments()`, which is called through a shared reference.
+    arguments: Mutex<Option<Result<<DB as Database>::Arguments<'args>, BoxDynError>>>,
+    persistent: bool,
+}
+
+impl<'args, DB: Database> BuiltQuery<'args, DB> {
+    /// If `true`, the statement will get prepared once and cached to the
+    /// connection's statement cache.
+    ///
+    /// If queried once with the flag set to `true`, all subsequent queries
+    /// matching the one with the flag will use the cached statement until the
+    /// cache is cleared.
+    ///
+    /// If `false`, the prepared statement will be closed after execution.
+    ///
+    /// Default: `true`.
+    pub fn persistent(mut self, value: bool) -> Self {
+        self.persistent = value;
+        self
+    }
+
+    /// The SQL of the query.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+}
+
+impl<'q, 'args, DB> Execute<'q, DB> for &'q BuiltQuery<'args, DB>
+where
+    DB: Database,
+    <DB as Database>::Arguments<'args>: IntoArguments<'q, DB>,
+{
+    fn sql(&self) -> &'q str {
+        &self.sql
+    }
+
+    fn statement(&self) -> Option<&<DB as Database>::Statement<'q>> {
+        None
+    }
+
+    fn take_arguments(&mut self) -> Result<Option<<DB as Database>::Arguments<'q>>, BoxDynError> {
+        let arguments = self
+            .arguments
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+
+        match arguments {
+            Some(arguments) => arguments.map(|arguments| Some(arguments.into_arguments())),
+            None => Err("a `BuiltQuery` can only be executed once".into()),
+        }
+    }
+
+    fn persistent(&self) -> bool {
+        self.persistent
+    }
+}
+
+/// Convert to a `sqlx::QueryBuilder`, with the same SQL and bind arguments, e.g. to hand a
+/// query to code that hasn't been migrated to fragments yet.
+///
+/// The SQL of the converted builder is its initial fragment, so [`reset()`][sqlx::QueryBuilder::reset]
+/// truncates it back to the point of conversion.
+///
+/// ### Errors
+/// Returns [`Error::Encode`] if a bind argument can't be encoded, and [`Error::AlreadyBuilt`] if
+/// the builder was built and not reset.
+impl<'args, DB: Database> TryFrom<QueryBuilder<'args, DB>> for sqlx::QueryBuilder<'args, DB> {
+    type Error = Error;
+
+    fn try_from(query_builder: QueryBuilder<'args, DB>) -> Result<Self, Error> {
+        query_builder.try_into_sqlx_query_builder()
+    }
+}
+
+/// Convert from a `sqlx::QueryBuilder`, with the same SQL and bind arguments.
+///
+/// The arguments of the `sqlx::QueryBuilder` are kept as with
+/// [`QueryBuilder::with_arguments()`]: more SQL, bind arguments and fragments can be pushed to the
+/// converted builder, but it can't itself be pushed to a builder that already has arguments.
+///
+/// This is only implemented for backends whose arguments don't borrow from the builder (Postgres
+/// and MySQL), since `sqlx::QueryBuilder` only gives access to its arguments through a borrow.
+/// In particular, it is not implemented for SQLite.
+///
+/// ### Panics
+/// If the `sqlx::QueryBuilder` was built and not reset.
+impl<'args, DB, A> From<sqlx::QueryBuilder<'args, DB>> for QueryBuilder<'args, DB>
+where
+    DB: for<'q> Database<Arguments<'q> = A>,
+    A: 'args + for<'q> IntoArguments<'q, DB>,
+{
+    fn from(mut query_builder: sqlx::QueryBuilder<'args, DB>) -> Self {
+        let arguments = query_builder
+            .build()
+            .take_arguments()
+            .ok()
+            .flatten()
+            .expect("BUG: `sqlx::QueryBuilder::build()` returned no arguments");
+        let query = query_builder.into_sql();
+
+        QueryBuilder {
+            init_len: query.len(),
+            query,
+            arguments: Some(arguments),
+            binds: Vec::new(),
+            built: None,
+        }
+    }
+}
+
+/// A wrapper around `QueryBuilder` for building a `WHERE` clause that is omitted if empty.
+///
+/// See [`QueryBuilder::where_clause()`] for details.
+#[allow(explicit_outlives_requirements)]
+pub struct WhereClause<'qb, 'args: 'qb, DB>
+where
+    DB: Database,
+{
+    query_builder: &'qb mut QueryBuilder<'args, DB>,
+    empty: bool,
+}
+
+impl<'qb, 'args: 'qb, DB> WhereClause<'qb, 'args, DB>
+where
+    DB: Database,
+{
+    /// Push ` WHERE ` or ` AND `, depending on whether this is the first condition.
+    fn push_keyword(&mut self) {
+        if self.empty {
+            self.query_builder.push(" WHERE ");
+            self.empty = false;
+        } else {
+            self.query_builder.push(" AND ");
+        }
+    }
+
+    /// Push a condition written as SQL.
+    ///
+    /// See [`QueryBuilder::push()`] for details.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        self.push_keyword();
+        self.query_builder.push(sql);
+
+        self
+    }
+
+    /// Push a condition written as a [`Fragment`] or another `QueryBuilder`, unless it is
+    /// [empty][PushFragment::is_empty].
+    ///
+    /// The fragment is pushed as-is, so a fragment containing a top-level `OR` should be
+    /// wrapped in parentheses, or pushed with [`.push_condition()`][Self::push_condition]. As
+    /// with [`QueryBuilder::push_fragment()`], a `&Fragment` can be pushed to reuse it, e.g. in
+    /// both a query and its `COUNT(*)` query.
+    pub fn push_fragment(&mut self, fragment: impl PushFragment<'args, DB>) -> &mut Self {
+        if !fragment.is_empty() {
+            self.push_keyword();
+            self.query_builder.push_fragment(fragment);
+        }
+
+        self
+    }
+
+    /// Push a [`Cond`], unless it is [empty][Cond::is_empty].
+    ///
+    /// Groups of several conditions are wrapped in parentheses. A group without members is
+    /// still pushed, e.g. an empty [`Cond::or()`] as `FALSE`, so that it doesn't match every row.
+    pub fn push_condition(&mut self, condition: Cond<'args, DB>) -> &mut Self {
+        if !condition.is_empty() {
+            self.push_keyword();
+            self.query_builder.push_fragment(condition.into_operand());
+        }
+
+        self
+    }
+
+    /// Push the conditions of a [`Filter`] that are set.
+    pub fn push_filter<F>(&mut self, filter: &F) -> &mut Self
+    where
+        F: Filter<'args, DB> + ?Sized,
+    {
+        filter.push_conditions(self);
+
+        self
+    }
+
+    /// Whether no condition has been pushed, i.e. nothing was written to the query.
+    pub fn is_empty(&self) -> bool {
+        self.empty
+    }
+}
+
+/// An iterator over the queries of a bulk `INSERT`, split to stay within the bind argument limit.
+///
+/// See [`QueryBuilder::chunked_values()`] for details.
+pub struct ChunkedValues<'args, DB, I, F>
+where
+    DB: Database,
+    I: Iterator,
+{
+    init: String,
+    rows: Peekable<I>,
+    push_row: F,
+    bind_limit: usize,
+    _db: PhantomData<fn() -> QueryBuilder<'args, DB>>,
+}
+
+impl<'args, DB, I, F> ChunkedValues<'args, DB, I, F>
+where
+    DB: Database,
+    I: Iterator,
+{
+    /// Set the maximum number of bind arguments in each query.
+    pub fn bind_limit(mut self, bind_limit: usize) -> Self {
+        self.bind_limit = bind_limit;
+        self
+    }
+}
+
+impl<'args, DB, I, F> Iterator for ChunkedValues<'args, DB, I, F>
+where
+    DB: Database,
+    I: Iterator,
+    F: FnMut(Separated<'_, 'args, DB, &'static str>, I::Item),
+{
+    type Item = QueryBuilder<'args, DB>;
+
+    fn next(&mut self) -> Option<QueryBuilder<'args, DB>> {
+        self.rows.peek()?;
+
+        let mut query_builder = QueryBuilder {
+            init_len: self.init.len(),
+            query: self.init.clone(),
+            arguments: Some(Default::default()),
+            binds: Vec::new(),
+            built: None,
+        };
+        query_builder.push("VALUES ");
+
+        let mut separated = query_builder.separated(", ");
+        // Number of bind arguments of the widest row so far, used to predict the next one.
+        let mut row_width = 0;
+        while let Some(row) = self
+            .rows
+            .next_if(|_| separated.query_builder.bind_count() + row_width <= self.bind_limit)
+        {
+            let before = separated.query_builder.bind_count();
+            separated.push_row(row, &mut self.push_row);
+
+            let after = separated.query_builder.bind_count();
+            assert!(
+                after <= self.bind_limit,
+                "`chunked_values()` row doesn't fit in the bind limit of {}",
+                self.bind_limit
+            );
+            row_width = row_width.max(after - before);
+        }
+        separated.finish();
+
+        Some(query_builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn pushed_query_builder_is_renumbered_after_existing_binds() {
+        use sqlx::Postgres;
+
+        use super::QueryBuilder;
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder
+            .push_bind(1)
+            .push(", ")
+            .push_bind(2)
+            .push(", ")
+            .push_bind(3);
+
+        let mut fragment: QueryBuilder<Postgres> = QueryBuilder::new(", ");
+        fragment.push_bind(4).push(" + '$1'::TEXT, ").push_bind(5);
+        query_builder.push_fragment(fragment);
+
+        assert_eq!(
+            query_builder.sql(),
+            "SELECT $1, $2, $3, $4 + '$1'::TEXT, $5"
+        );
+        assert_eq!(query_builder.bind_count(), 5);
+        assert_eq!(
+            query_builder.to_debug_sql().to_string(),
+            "SELECT 1, 2, 3, 4 + '$1'::TEXT, 5"
+        );
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn debug_sql_skips_mysql_escaped_quotes_and_comments() {
+        use sqlx::MySql;
+
+        use super::QueryBuilder;
+
+        let mut query_builder: QueryBuilder<MySql> =
+            QueryBuilder::new(r"SELECT * FROM t WHERE a = 'it\'s ?' # ?");
+        query_builder.push("\n AND b = ");
+        query_builder.push_bind("x");
+
+        assert_eq!(
+            query_builder.to_debug_sql().to_string(),
+            "SELECT * FROM t WHERE a = 'it\\'s ?' # ?\n AND b = 'x'"
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn try_build_checks_the_bind_limit() {
+        use sqlx::Sqlite;
+
+        use super::QueryBuilder;
+        use crate::dialect::Dialect;
+        use crate::error::Error;
+
+        let limit = Dialect::of::<Sqlite>().bind_limit();
+        let mut query_builder: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT ");
+        let mut separated = query_builder.separated(", ");
+        for i in 0..=limit {
+            separated.push_bind(i as i64);
+        }
+        separated.finish();
+
+        assert!(matches!(
+            query_builder.try_build(),
+            Err(Error::TooManyArguments { limit: l }) if l == limit
+        ));
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn conversion_to_sqlx_query_builder_returns_encode_errors() {
+        use sqlx::encode::{Encode, IsNull};
+        use sqlx::error::BoxDynError;
+        use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
+        use sqlx::{Postgres, Type};
+
+        use super::QueryBuilder;
+        use crate::error::Error;
+
+        #[derive(Debug)]
+        struct Unencodable;
+
+        impl Type<Postgres> for Unencodable {
+            fn type_info() -> PgTypeInfo {
+                <i32 as Type<Postgres>>::type_info()
+            }
+        }
+
+        impl Encode<'_, Postgres> for Unencodable {
+            fn encode_by_ref(&self, _: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                Err("unencodable".into())
+            }
+        }
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push_bind(1).push(", ").push_bind(2);
+        let converted = sqlx::QueryBuilder::try_from(query_builder).unwrap();
+        assert_eq!(converted.sql(), "SELECT $1, $2");
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push_bind(Unencodable);
+        assert!(matches!(
+            sqlx::QueryBuilder::try_from(query_builder),
+            Err(Error::Encode(_))
+        ));
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn separated_is_ended_by_finish() {
+        use sqlx::{Execute, Postgres};
+
+        use super::QueryBuilder;
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        let mut list = query_builder.separated(", ").delimiters("(", ")");
+        list.push_bind(1).push_bind(2);
+        assert_eq!(list.finish(), 2);
+        query_builder.push(" AND ");
+        let list = query_builder.separated(" OR ").if_empty("TRUE");
+        assert_eq!(list.finish(), 0);
+        query_builder.push(" AND ");
+        let mut list = query_builder
+            .separated(", ")
+            .delimiters("(", ")")
+            .if_empty("NULL");
+        list.push_bind(3);
+        assert_eq!(list.finish(), 1);
+
+        assert_eq!(query_builder.sql(), "SELECT ($1, $2) AND TRUE AND ($3)");
+
+        // The builder can be used again while a plain list is still in scope.
+        let mut query_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT * FROM t WHERE id IN (");
+        let mut separated = query_builder.separated(", ");
+        separated.push_bind(1);
+        separated.push_unseparated(")");
+        assert_eq!(
+            query_builder.build().sql(),
+            "SELECT * FROM t WHERE id IN ($1)"
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn built_query_hands_out_its_arguments_once() {
+        use sqlx::{Arguments, Execute, Postgres};
+
+        use super::QueryBuilder;
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push_bind(1).push(", ").push_bind("two");
+        let query = query_builder.into_query();
+        assert_eq!(query.sql(), "SELECT $1, $2");
+
+        let mut execute = &query;
+        assert_eq!(Execute::sql(&execute), "SELECT $1, $2");
+        assert!(execute.persistent());
+
+        let arguments = execute.take_arguments().unwrap().unwrap();
+        assert_eq!(arguments.len(), 2);
+        assert!(execute.take_arguments().is_err());
+
+        let query = QueryBuilder::<Postgres>::new("SELECT 1")
+            .into_query()
+            .persistent(false);
+        assert!(!(&query).persistent());
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn built_query_returns_encode_errors_on_execution() {
+        use sqlx::encode::{Encode, IsNull};
+        use sqlx::error::BoxDynError;
+        use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
+        use sqlx::{Execute, Postgres, Type};
+
+        use super::QueryBuilder;
+
+        #[derive(Debug)]
+        struct Unencodable;
+
+        impl Type<Postgres> for Unencodable {
+            fn type_info() -> PgTypeInfo {
+                <i32 as Type<Postgres>>::type_info()
+            }
+        }
+
+        impl Encode<'_, Postgres> for Unencodable {
+            fn encode_by_ref(&self, _: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                Err("unencodable".into())
+            }
+        }
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push_bind(Unencodable);
+        let query = query_builder.into_query();
+
+        let mut execute = &query;
+        let Err(error) = execute.take_arguments() else {
+            panic!("encoding should fail");
+        };
+        assert_eq!(error.to_string(), "unencodable");
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn conversion_from_sqlx_query_builder_keeps_its_arguments() {
+        use sqlx::{Arguments, Execute, Postgres};
+
+        use super::QueryBuilder;
+        use crate::error::Error;
+        use crate::fragment::Fragment;
+
+        let mut legacy: sqlx::QueryBuilder<Postgres> =
+            sqlx::QueryBuilder::new("SELECT * FROM t WHERE a = ");
+        legacy.push_bind(1).push(" AND b = ").push_bind("two");
+
+        let mut query_builder = QueryBuilder::from(legacy);
+        query_builder.push(" AND c = ").push_bind(3);
+        let mut fragment = Fragment::new(" AND d = ");
+        fragment.push_bind(4);
+        query_builder.push_fragment(fragment);
+
+        assert_eq!(
+            query_builder.sql(),
+            "SELECT * FROM t WHERE a = $1 AND b = $2 AND c = $3 AND d = $4"
+        );
+        let mut query = query_builder.try_build().unwrap();
+        assert_eq!(query.take_arguments().unwrap().unwrap().len(), 4);
+
+        let mut legacy: sqlx::QueryBuilder<Postgres> = sqlx::QueryBuilder::new("a = ");
+        legacy.push_bind(1);
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push_bind(0).push(", ");
+        assert!(matches!(
+            query_builder.try_push_fragment(QueryBuilder::from(legacy)),
+            Err(Error::ArgumentsMerge)
+        ));
+        assert_eq!(query_builder.sql(), "SELECT $1, ");
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn debug_sql_after_build_keeps_placeholders() {
+        use sqlx::Postgres;
+
+        use super::QueryBuilder;
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT ");
+        query_builder.push_bind(1).push(", ").push_bind("two");
+        assert_eq!(query_builder.to_debug_sql().to_string(), "SELECT 1, 'two'");
+
+        let _ = query_builder.build();
+        assert_eq!(query_builder.to_debug_sql().to_string(), "SELECT $1, $2");
+
+        query_builder.reset();
+        assert_eq!(query_builder.to_debug_sql().to_string(), "SELECT ");
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn where_clause_reuses_fragments_by_reference() {
+        use sqlx::Postgres;
+
+        use super::QueryBuilder;
+        use crate::fragment::Fragment;
+
+        let mut filter = Fragment::new("age > ");
+        filter.push_bind(18);
+        let empty = Fragment::default();
+
+        let mut data: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users");
+        data.where_clause()
+            .push_fragment(&empty)
+            .push_fragment(&filter);
+        let mut count: QueryBuilder<Postgres> = QueryBuilder::new("SELECT COUNT(*) FROM users");
+        count
+            .where_clause()
+            .push_fragment(&filter)
+            .push_fragment(QueryBuilder::new(""));
+
+        assert_eq!(data.sql(), "SELECT * FROM users WHERE age > $1");
+        assert_eq!(count.sql(), "SELECT COUNT(*) FROM users WHERE age > $1");
+    }
+}
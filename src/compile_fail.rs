@@ -0,0 +1,67 @@
+//! Compile-time errors of `fragment!()`, checked as `compile_fail` doctests.
+//!
+//! They use `sqlx::Any` so that they don't depend on the backend features. This one compiles:
+//!
+//! ```rust
+//! let name = "x";
+//! let _: sqlx_fragment::Fragment<sqlx::Any> =
+//!     sqlx_fragment::fragment!("(a = {} OR b = '(') AND c = {name} -- )", 1);
+//! ```
+//!
+//! Unbalanced parentheses:
+//!
+//! ```compile_fail
+//! let _: sqlx_fragment::Fragment<sqlx::Any> = sqlx_fragment::fragment!("(a = {}", 1);
+//! ```
+//!
+//! ```compile_fail
+//! let _: sqlx_fragment::Fragment<sqlx::Any> = sqlx_fragment::fragment!("a = {})", 1);
+//! ```
+//!
+//! Unterminated quotes and comments:
+//!
+//! ```compile_fail
+//! let _: sqlx_fragment::Fragment<sqlx::Any> = sqlx_fragment::fragment!("a = {} AND b = 'x", 1);
+//! ```
+//!
+//! ```compile_fail
+//! let _: sqlx_fragment::Fragment<sqlx::Any> = sqlx_fragment::fragment!("\"a = {}", 1);
+//! ```
+//!
+//! ```compile_fail
+//! let _: sqlx_fragment::Fragment<sqlx::Any> = sqlx_fragment::fragment!("a = {} /* x", 1);
+//! ```
+//!
+//! Placeholders inside literals and comments:
+//!
+//! ```compile_fail
+//! let _: sqlx_fragment::Fragment<sqlx::Any> = sqlx_fragment::fragment!("a = '{}'", 1);
+//! ```
+//!
+//! ```compile_fail
+//! let _: sqlx_fragment::Fragment<sqlx::Any> = sqlx_fragment::fragment!("a = 1 -- {}", 1);
+//! ```
+//!
+//! Literal `$N` placeholders, unless only MySQL is selected:
+//!
+//! ```compile_fail
+//! # #[cfg(all(feature = "mysql", not(feature = "postgres"), not(feature = "sqlite")))]
+//! # compile_error!("`$1` is not a placeholder in MySQL");
+//! let _: sqlx_fragment::Fragment<sqlx::Any> = sqlx_fragment::fragment!("a = $1 AND b = {}", 1);
+//! ```
+//!
+//! Literal `?` placeholders, unless Postgres is selected:
+//!
+//! ```compile_fail
+//! # #[cfg(feature = "postgres")]
+//! # compile_error!("`?` is an operator in Postgres");
+//! let _: sqlx_fragment::Fragment<sqlx::Any> = sqlx_fragment::fragment!("a = ? AND b = {}", 1);
+//! ```
+//!
+//! Fragments and raw SQL used by several placeholders:
+//!
+//! ```compile_fail
+//! let other: sqlx_fragment::Fragment<sqlx::Any> = sqlx_fragment::fragment!("b = {}", 1);
+//! let _: sqlx_fragment::Fragment<sqlx::Any> =
+//!     sqlx_fragment::fragment!("{other:frag} AND {other:frag}");
+//! ```
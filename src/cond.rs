@@ -0,0 +1,250 @@
+//! Boolean combinators over fragments.
+
+use sqlx::database::Database;
+
+use crate::fragment::Fragment;
+
+/// A boolean condition built from [`Fragment`]s, for `WHERE` and `HAVING` clauses.
+///
+/// Conditions are combined with [`Cond::and()`], [`Cond::or()`] and [`Cond::not()`], and pushed
+/// with [`QueryBuilder::push_condition()`][crate::builder2::QueryBuilder::push_condition].
+/// Nested groups are wrapped in parentheses; fragments themselves are pushed as-is, so a
+/// fragment containing a top-level `OR` should be a group of its own.
+///
+/// An empty fragment stands for "no condition" and is dropped from the group it belongs to,
+/// which makes optional filters easy to express. A group with no members left is not dropped:
+/// an empty `AND` is `TRUE` and an empty `OR` is `FALSE`, wherever they appear, so that e.g.
+/// `role = ANY` of an empty set of roles matches nothing.
+///
+/// ```rust
+/// # #[cfg(feature = "postgres")] {
+/// use sqlx::Postgres;
+/// use sqlx_fragment::builder2::QueryBuilder;
+/// use sqlx_fragment::{fragment, Cond, Fragment};
+///
+/// let name: Option<&str> = None;
+/// let name_filter = match name {
+///     Some(name) => fragment!("name = {name}"),
+///     None => Fragment::default(),
+/// };
+///
+/// let condition = Cond::and([
+///     Cond::from(fragment!("age > {}", 18)),
+///     Cond::from(name_filter),
+///     Cond::or([fragment!("role = {}", "admin"), fragment!("role = {}", "owner")]),
+/// ]);
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users WHERE ");
+/// query_builder.push_condition(condition);
+///
+/// assert_eq!(
+///     query_builder.sql(),
+///     "SELECT * FROM users WHERE age > $1 AND (role = $2 OR role = $3)"
+/// );
+/// # }
+/// ```
+pub enum Cond<'args, DB: Database> {
+    /// A single condition.
+    Fragment(Fragment<'args, DB>),
+    /// True if every member is true.
+    And(Vec<Cond<'args, DB>>),
+    /// True if any member is true.
+    Or(Vec<Cond<'args, DB>>),
+    /// True if the inner condition is false.
+    Not(Box<Cond<'args, DB>>),
+}
+
+impl<'args, DB: Database> Clone for Cond<'args, DB> {
+    fn clone(&self) -> Self {
+        match self {
+            Cond::Fragment(fragment) => Cond::Fragment(fragment.clone()),
+            Cond::And(members) => Cond::And(members.clone()),
+            Cond::Or(members) => Cond::Or(members.clone()),
+            Cond::Not(inner) => Cond::Not(inner.clone()),
+        }
+    }
+}
+
+impl<'args, DB: Database> From<Fragment<'args, DB>> for Cond<'args, DB> {
+    fn from(fragment: Fragment<'args, DB>) -> Self {
+        Cond::Fragment(fragment)
+    }
+}
+
+impl<'args, DB: Database> Cond<'args, DB> {
+    /// A condition that is true if every member is true.
+    pub fn and<I>(members: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Cond<'args, DB>>,
+    {
+        Cond::And(members.into_iter().map(Into::into).collect())
+    }
+
+    /// A condition that is true if any member is true.
+    pub fn or<I>(members: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Cond<'args, DB>>,
+    {
+        Cond::Or(members.into_iter().map(Into::into).collect())
+    }
+
+    /// A condition that is true if `inner` is false.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(inner: impl Into<Cond<'args, DB>>) -> Self {
+        Cond::Not(Box::new(inner.into()))
+    }
+
+    /// Whether this condition stands for "no condition", i.e. is an empty fragment, possibly
+    /// negated.
+    ///
+    /// Groups are never empty, even without members.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Cond::Fragment(fragment) => fragment.is_empty(),
+            Cond::And(_) | Cond::Or(_) => false,
+            Cond::Not(inner) => inner.is_empty(),
+        }
+    }
+
+    /// Render this condition as a fragment.
+    ///
+    /// An empty condition renders as `TRUE`.
+    pub fn into_fragment(self) -> Fragment<'args, DB> {
+        let mut fragment = Fragment::default();
+
+        if self.is_empty() {
+            fragment.push("TRUE");
+        } else {
+            self.render(&mut fragment, false);
+        }
+
+        fragment
+    }
+
+    /// Render this non-empty condition as a fragment that can be combined with other conditions,
+    /// i.e. with parentheses around groups.
+    pub(crate) fn into_operand(self) -> Fragment<'args, DB> {
+        let mut fragment = Fragment::default();
+        self.render(&mut fragment, true);
+        fragment
+    }
+
+    /// Append this non-empty condition to `out`, with parentheses if it is a group within
+    /// another group.
+    fn render(self, out: &mut Fragment<'args, DB>, nested: bool) {
+        let (members, separator, if_empty) = match self {
+            Cond::Fragment(fragment) => {
+                out.push_fragment(fragment);
+                return;
+            }
+            Cond::Not(inner) => {
+                out.push("NOT (");
+                inner.render(out, false);
+                out.push(")");
+                return;
+            }
+            Cond::And(members) => (members, " AND ", "TRUE"),
+            Cond::Or(members) => (members, " OR ", "FALSE"),
+        };
+
+        let mut members: Vec<_> = members.into_iter().filter(|m| !m.is_empty()).collect();
+        if members.is_empty() {
+            out.push(if_empty);
+            return;
+        }
+        if members.len() == 1 {
+            members.pop().unwrap().render(out, nested);
+            return;
+        }
+
+        if nested {
+            out.push("(");
+        }
+        for (i, member) in members.into_iter().enumerate() {
+            if i > 0 {
+                out.push(separator);
+            }
+            member.render(out, true);
+        }
+        if nested {
+            out.push(")");
+        }
+    }
+}
+
+impl<'args, DB: Database> From<Cond<'args, DB>> for Fragment<'args, DB> {
+    fn from(cond: Cond<'args, DB>) -> Self {
+        cond.into_fragment()
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use sqlx::Postgres;
+
+    use super::Cond;
+    use crate::builder2::QueryBuilder;
+    use crate::fragment::Fragment;
+
+    fn a() -> Fragment<'static, Postgres> {
+        let mut fragment = Fragment::new("a = ");
+        fragment.push_bind(1);
+        fragment
+    }
+
+    fn render(condition: Cond<'static, Postgres>) -> String {
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("");
+        query_builder.push_condition(condition);
+        query_builder.into_sql()
+    }
+
+    #[test]
+    fn empty_fragments_are_skipped() {
+        assert_eq!(render(Cond::and([a(), Fragment::default()])), "a = $1");
+        assert_eq!(render(Cond::or([Fragment::default(), a()])), "a = $1");
+        assert_eq!(render(Cond::from(Fragment::default())), "TRUE");
+        assert_eq!(render(Cond::not(Fragment::default())), "TRUE");
+    }
+
+    #[test]
+    fn empty_groups_are_true_or_false() {
+        assert_eq!(render(Cond::and(Vec::<Cond<_>>::new())), "TRUE");
+        assert_eq!(render(Cond::or(Vec::<Cond<_>>::new())), "FALSE");
+        assert_eq!(render(Cond::or([Fragment::default()])), "FALSE");
+        assert_eq!(
+            render(Cond::and([
+                Cond::from(a()),
+                Cond::or(Vec::<Cond<_>>::new())
+            ])),
+            "a = $1 AND FALSE"
+        );
+        assert_eq!(
+            render(Cond::or([
+                Cond::from(a()),
+                Cond::and(Vec::<Cond<_>>::new())
+            ])),
+            "a = $1 OR TRUE"
+        );
+        assert_eq!(
+            render(Cond::not(Cond::and(Vec::<Cond<_>>::new()))),
+            "NOT (TRUE)"
+        );
+        assert_eq!(
+            render(Cond::not(Cond::or(Vec::<Cond<_>>::new()))),
+            "NOT (FALSE)"
+        );
+    }
+
+    #[test]
+    fn where_clause_keeps_empty_groups() {
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM t");
+        query_builder
+            .where_clause()
+            .push_condition(Cond::and([Fragment::default()]))
+            .push_condition(Cond::or(Vec::<Cond<_>>::new()))
+            .push_condition(Cond::from(Fragment::default()));
+        assert_eq!(query_builder.sql(), "SELECT * FROM t WHERE TRUE AND FALSE");
+    }
+}
@@ -0,0 +1,89 @@
+//! Rendering of queries with their bind arguments inlined, for debugging.
+
+use std::fmt::{self, Debug, Display};
+
+use crate::dialect::Dialect;
+
+/// A query with its bind arguments inlined as SQL literals, returned by
+/// [`QueryBuilder::to_debug_sql()`][crate::builder2::QueryBuilder::to_debug_sql].
+///
+/// **This is not safe to execute.** The literals are rendered on a best-effort basis and are
+/// not guaranteed to round-trip, so this type deliberately only implements `Display` and
+/// `Debug`, for log lines and test failure messages.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DebugSql(pub(crate) String);
+
+impl Display for DebugSql {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Debug for DebugSql {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+/// Write `value` to `out` as a SQL literal, based on its `Debug` representation.
+pub(crate) fn write_literal(value: &dyn Debug, dialect: Dialect, out: &mut String) {
+    write_debug_literal(&format!("{value:?}"), dialect, out);
+}
+
+fn write_debug_literal(debug: &str, dialect: Dialect, out: &mut String) {
+    if debug == "None" {
+        out.push_str("NULL");
+    } else if let Some(inner) = debug
+        .strip_prefix("Some(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        write_debug_literal(inner, dialect, out);
+    } else if debug == "true" || debug == "false" {
+        out.push_str(&debug.to_uppercase());
+    } else if is_number(debug) {
+        out.push_str(debug);
+    } else if let Some(string) = unescape_debug_str(debug) {
+        dialect.quote_literal(&string, out);
+    } else {
+        // Dates, UUIDs, etc.: hope that the database can parse their `Debug` representation.
+        dialect.quote_literal(debug, out);
+    }
+}
+
+/// Whether `debug` is an integer or a finite float (i.e. not `NaN` or `inf`).
+fn is_number(debug: &str) -> bool {
+    debug.starts_with(|c: char| c.is_ascii_digit() || c == '-')
+        && debug
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        && debug.parse::<f64>().is_ok()
+}
+
+/// The value of the `Debug` representation of a `str` or `char`, or `None` if `debug` isn't one.
+fn unescape_debug_str(debug: &str) -> Option<String> {
+    let quote = debug.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let inner = debug.strip_prefix(quote)?.strip_suffix(quote)?;
+
+    let mut string = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            string.push(c);
+            continue;
+        }
+
+        string.push(match chars.next()? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            '0' => '\0',
+            'u' => {
+                let hex: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+            }
+            c => c,
+        });
+    }
+
+    Some(string)
+}
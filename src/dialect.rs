@@ -0,0 +1,154 @@
+//! Backend-specific SQL syntax.
+
+use std::fmt::Write;
+
+use sqlx::database::Database;
+
+use crate::lexer::Syntax;
+
+/// The SQL dialect spoken by a [`Database`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+    Mssql,
+    /// Any other backend; assumed to follow the SQL standard.
+    Other,
+}
+
+impl Dialect {
+    /// The dialect of `DB`, chosen from [`Database::NAME`].
+    pub fn of<DB: Database>() -> Self {
+        match DB::NAME {
+            "PostgreSQL" => Dialect::Postgres,
+            "MySQL" => Dialect::MySql,
+            "SQLite" => Dialect::Sqlite,
+            "MSSQL" => Dialect::Mssql,
+            _ => Dialect::Other,
+        }
+    }
+
+    /// The syntax of bind placeholders.
+    pub fn placeholder_style(self) -> PlaceholderStyle {
+        match self {
+            Dialect::Postgres => PlaceholderStyle::Numbered,
+            Dialect::MySql | Dialect::Sqlite | Dialect::Mssql | Dialect::Other => {
+                PlaceholderStyle::Positional
+            }
+        }
+    }
+
+    /// The default maximum number of bind arguments in a single query.
+    ///
+    /// See [`QueryBuilder::push_bind()`][crate::builder2::QueryBuilder::push_bind] for sources.
+    pub fn bind_limit(self) -> usize {
+        match self {
+            Dialect::Postgres | Dialect::MySql => 65535,
+            Dialect::Sqlite => 32766,
+            Dialect::Mssql => 2100,
+            // SQLite prior to 3.32.0 has the lowest limit we know of.
+            Dialect::Other => 999,
+        }
+    }
+
+    /// The lexical rules of the dialect, for [`Lexer`][crate::lexer::Lexer].
+    pub fn syntax(self) -> Syntax {
+        Syntax {
+            backslash_escapes: self == Dialect::MySql,
+            hash_comments: self == Dialect::MySql,
+            dollar_quotes: self == Dialect::Postgres,
+        }
+    }
+
+    /// Whether row values can be compared with `<` and `>`, as in `(a, b) > (1, 2)`.
+    pub fn has_row_comparison(self) -> bool {
+        match self {
+            Dialect::Postgres | Dialect::MySql | Dialect::Sqlite => true,
+            // Not supported by SQL Server, and optional in the SQL standard.
+            Dialect::Mssql | Dialect::Other => false,
+        }
+    }
+
+    /// Whether `ORDER BY` accepts `NULLS FIRST` and `NULLS LAST`.
+    pub fn has_nulls_ordering(self) -> bool {
+        match self {
+            Dialect::Postgres | Dialect::Sqlite => true,
+            Dialect::MySql | Dialect::Mssql | Dialect::Other => false,
+        }
+    }
+
+    /// Write `ident` to `out` as a quoted identifier.
+    ///
+    /// MySQL uses backticks; everything else uses the standard double quotes. Quote characters
+    /// within `ident` are escaped by doubling them.
+    pub fn quote_ident(self, ident: &str, out: &mut String) {
+        let quote = match self {
+            Dialect::MySql => '`',
+            Dialect::Postgres | Dialect::Sqlite | Dialect::Mssql | Dialect::Other => '"',
+        };
+
+        out.reserve(ident.len() + 2);
+        out.push(quote);
+        for c in ident.chars() {
+            if c == quote {
+                out.push(quote);
+            }
+            out.push(c);
+        }
+        out.push(quote);
+    }
+
+    /// Write `value` to `out` as a string literal.
+    ///
+    /// Single quotes are escaped by doubling them. MySQL also treats backslashes as escape
+    /// characters by default, so they are doubled as well.
+    pub fn quote_literal(self, value: &str, out: &mut String) {
+        out.reserve(value.len() + 2);
+        out.push('\'');
+        for c in value.chars() {
+            match c {
+                '\'' => out.push('\''),
+                '\\' if self == Dialect::MySql => out.push('\\'),
+                _ => {}
+            }
+            out.push(c);
+        }
+        out.push('\'');
+    }
+
+    /// Write a `.`-separated list of quoted identifiers, e.g. `"schema"."table"."column"`.
+    pub fn quote_qualified_ident<I>(self, parts: I, out: &mut String)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        for (i, part) in parts.into_iter().enumerate() {
+            if i > 0 {
+                out.push('.');
+            }
+            self.quote_ident(part.as_ref(), out);
+        }
+    }
+}
+
+/// The syntax used by a backend for bind placeholders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PlaceholderStyle {
+    /// `?`, bound in order of appearance (MySQL, SQLite).
+    Positional,
+    /// `$N`, bound by number (Postgres).
+    Numbered,
+}
+
+impl PlaceholderStyle {
+    /// Write the placeholder for the `index`th (1-based) bind argument.
+    pub fn write(self, index: usize, out: &mut String) {
+        match self {
+            PlaceholderStyle::Positional => out.push('?'),
+            PlaceholderStyle::Numbered => {
+                write!(out, "${index}").expect("error writing placeholder");
+            }
+        }
+    }
+}
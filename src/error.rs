@@ -0,0 +1,80 @@
+//! Error type for fallible builder operations.
+
+use std::fmt::{self, Display};
+
+use sqlx::error::BoxDynError;
+
+/// An error raised while building a query.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A `:name` parameter was used in the SQL, but no argument with that name was given.
+    MissingNamedArgument(String),
+    /// An argument was given by name, but the SQL doesn't use it.
+    UnusedNamedArgument(String),
+    /// A bind argument could not be encoded for the database.
+    Encode(BoxDynError),
+    /// The query would have more bind arguments than the backend accepts.
+    TooManyArguments {
+        /// The maximum number of bind arguments.
+        limit: usize,
+    },
+    /// The `QueryBuilder` was used after being built, without being reset first.
+    AlreadyBuilt,
+    /// The number of placeholders in the SQL doesn't match the number of bind arguments.
+    PlaceholderMismatch {
+        /// The number of placeholders, or the highest placeholder number for `$N` placeholders.
+        placeholders: usize,
+        /// The number of bind arguments.
+        arguments: usize,
+    },
+    /// A `QueryBuilder` made with `with_arguments()` was pushed to a builder that already has
+    /// bind arguments, which would require renumbering arguments that can't be iterated.
+    ArgumentsMerge,
+    /// A pagination cursor token couldn't be decoded, or doesn't match the sort columns.
+    InvalidCursor,
+    /// A sort order given by the user refers to a column that isn't in the whitelist.
+    UnknownSortColumn(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingNamedArgument(name) => {
+                write!(f, "no argument given for the named parameter `:{name}`")
+            }
+            Error::UnusedNamedArgument(name) => {
+                write!(f, "named argument `{name}` is not used in the query")
+            }
+            Error::Encode(error) => write!(f, "error encoding a bind argument: {error}"),
+            Error::TooManyArguments { limit } => {
+                write!(f, "the query exceeds the limit of {limit} bind arguments")
+            }
+            Error::AlreadyBuilt => {
+                write!(f, "QueryBuilder must be reset before reuse after `.build()`")
+            }
+            Error::PlaceholderMismatch {
+                placeholders,
+                arguments,
+            } => write!(
+                f,
+                "the query has {placeholders} placeholders but {arguments} bind arguments"
+            ),
+            Error::ArgumentsMerge => write!(
+                f,
+                "cannot merge arguments given to `QueryBuilder::with_arguments()` into a non-empty `QueryBuilder`"
+            ),
+            Error::InvalidCursor => write!(f, "invalid pagination cursor"),
+            Error::UnknownSortColumn(key) => write!(f, "cannot sort by unknown column `{key}`"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Encode(error) => Some(&**error),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Structs of optional filters, turned into `WHERE` conditions.
+
+use sqlx::database::Database;
+
+use crate::builder2::WhereClause;
+
+/// A set of optional conditions, usually parsed from the query string of a list endpoint.
+///
+/// This is usually derived with `#[derive(Filter)]`; see [`derive@Filter`] for the generated
+/// conditions. Filters are pushed with
+/// [`WhereClause::push_filter()`][crate::builder2::WhereClause::push_filter].
+pub trait Filter<'args, DB: Database> {
+    /// Push each condition that is set to `where_clause`.
+    fn push_conditions(&self, where_clause: &mut WhereClause<'_, 'args, DB>);
+}
@@ -0,0 +1,342 @@
+//! Database-independent chunks of SQL with deferred bind arguments.
+
+use std::fmt::Write;
+use std::fmt::{Debug, Display};
+
+use sqlx::database::Database;
+use sqlx::encode::Encode;
+use sqlx::types::Type;
+
+use crate::arguments::Shared;
+use crate::builder2::QueryBuilder;
+use crate::dialect::Dialect;
+use crate::error::Error;
+use crate::named::{self, NamedArguments};
+
+/// A chunk of SQL with its bind arguments, meant to be spliced into a [`QueryBuilder`].
+///
+/// Unlike a `QueryBuilder`, a `Fragment` doesn't write any placeholder syntax: bind arguments
+/// are kept aside, type-erased, and only get a placeholder (`?` or `$N` for Postgres) and a slot
+/// in the query arguments when the fragment is pushed with
+/// [`QueryBuilder::push_fragment()`]. Fragments can thus be built independently of each other
+/// and combined in any order.
+///
+/// Bound values are reference-counted, and cloned when the fragment is pushed to a
+/// `QueryBuilder`. Cloning a `Fragment` is thus cheap, and the same fragment can be pushed to
+/// several queries, e.g. a data query and the matching `COUNT(*)` query:
+///
+/// ```rust
+/// # #[cfg(feature = "postgres")] {
+/// use sqlx::Postgres;
+/// use sqlx_fragment::builder2::QueryBuilder;
+/// use sqlx_fragment::Fragment;
+///
+/// let mut filter = Fragment::new("age > ");
+/// filter.push_bind(18);
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users WHERE id = ");
+/// query_builder.push_bind(42);
+/// query_builder.push(" AND ");
+/// query_builder.push_fragment(&filter);
+///
+/// assert_eq!(query_builder.sql(), "SELECT * FROM users WHERE id = $1 AND age > $2");
+///
+/// let mut count_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT COUNT(*) FROM users WHERE ");
+/// count_builder.push_fragment(&filter);
+///
+/// assert_eq!(count_builder.sql(), "SELECT COUNT(*) FROM users WHERE age > $1");
+/// # }
+/// ```
+pub struct Fragment<'args, DB>
+where
+    DB: Database,
+{
+    segments: Vec<Segment>,
+    binds: Vec<Shared<'args, DB>>,
+}
+
+/// A piece of a [`Fragment`]: either literal SQL or the position of a bind argument.
+#[derive(Clone)]
+enum Segment {
+    Sql(String),
+    /// The next bind argument.
+    Bind,
+    /// The bind argument at the given index, used again.
+    Repeat(usize),
+}
+
+impl<'args, DB: Database> Clone for Fragment<'args, DB> {
+    fn clone(&self) -> Self {
+        Fragment {
+            segments: self.segments.clone(),
+            binds: self.binds.clone(),
+        }
+    }
+}
+
+impl<'args, DB: Database> Default for Fragment<'args, DB> {
+    fn default() -> Self {
+        Fragment {
+            segments: Vec::new(),
+            binds: Vec::new(),
+        }
+    }
+}
+
+impl<'args, DB: Database> Fragment<'args, DB> {
+    /// Start building a fragment with an initial SQL string, which may be empty.
+    pub fn new(init: impl Into<String>) -> Self {
+        let mut fragment = Fragment::default();
+        let init = init.into();
+        if !init.is_empty() {
+            fragment.segments.push(Segment::Sql(init));
+        }
+        fragment
+    }
+
+    /// Append SQL to the fragment.
+    ///
+    /// See [`QueryBuilder::push()`] for details, and beware of SQL injection.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        if let Some(Segment::Sql(last)) = self.segments.last_mut() {
+            write!(last, "{sql}").expect("error formatting `sql`");
+        } else {
+            self.segments.push(Segment::Sql(sql.to_string()));
+        }
+
+        self
+    }
+
+    /// Append a quoted identifier.
+    ///
+    /// See [`QueryBuilder::push_ident()`] for details.
+    pub fn push_ident(&mut self, ident: &str) -> &mut Self {
+        let mut quoted = String::new();
+        Dialect::of::<DB>().quote_ident(ident, &mut quoted);
+        self.push(quoted)
+    }
+
+    /// Append a qualified identifier, such as `schema.table.column`, quoting each part.
+    ///
+    /// See [`QueryBuilder::push_ident()`] for details.
+    pub fn push_qualified_ident<I>(&mut self, parts: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut quoted = String::new();
+        Dialect::of::<DB>().quote_qualified_ident(parts, &mut quoted);
+        self.push(quoted)
+    }
+
+    /// Append a bind argument.
+    ///
+    /// The value is stored until the fragment is pushed to a [`QueryBuilder`], at which point the
+    /// matching placeholder is written and a clone of the value is bound.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + Send + Sync + Clone + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.segments.push(Segment::Bind);
+        self.binds.push(Shared::new(value));
+
+        self
+    }
+
+    /// Append a `column IN (...)` predicate, with one bind argument per value, or `FALSE` if
+    /// `values` is empty.
+    ///
+    /// See [`QueryBuilder::push_in()`] for details.
+    pub fn push_in<I>(&mut self, column: impl Display, values: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: 'args + Send + Sync + Clone + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.push_in_list(column, " IN (", "FALSE", values)
+    }
+
+    /// Append a `column NOT IN (...)` predicate, with one bind argument per value, or `TRUE` if
+    /// `values` is empty.
+    ///
+    /// See [`QueryBuilder::push_in()`] for details.
+    pub fn push_not_in<I>(&mut self, column: impl Display, values: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: 'args + Send + Sync + Clone + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.push_in_list(column, " NOT IN (", "TRUE", values)
+    }
+
+    fn push_in_list<I>(
+        &mut self,
+        column: impl Display,
+        operator: &str,
+        if_empty: &str,
+        values: I,
+    ) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: 'args + Send + Sync + Clone + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        let mut values = values.into_iter().peekable();
+        if values.peek().is_none() {
+            return self.push(if_empty);
+        }
+
+        self.push(column);
+        self.push(operator);
+        for (i, value) in values.enumerate() {
+            if i > 0 {
+                self.push(", ");
+            }
+            self.push_bind(value);
+        }
+        self.push(")")
+    }
+
+    /// Append a placeholder for the bind argument at `index`, used a second time.
+    pub(crate) fn push_repeat(&mut self, index: usize) -> &mut Self {
+        assert!(index < self.binds.len(), "BUG: repeated bind out of range");
+        self.segments.push(Segment::Repeat(index));
+
+        self
+    }
+
+    /// Append SQL with named parameters, such as `:name`, taking their values from `arguments`.
+    ///
+    /// A parameter that is used several times is only bound once with backends that support
+    /// numbered placeholders (`$N` for Postgres), and bound several times with the others.
+    /// Named parameters in string literals, quoted identifiers and comments are ignored, as are
+    /// `::` casts.
+    ///
+    /// ### Errors
+    /// Returns an error, and leaves the fragment unchanged, if a parameter has no matching
+    /// argument ([`Error::MissingNamedArgument`]) or if an argument isn't used
+    /// ([`Error::UnusedNamedArgument`]).
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use std::collections::HashMap;
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// let arguments = HashMap::from([("min", 1), ("max", 10)]);
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM t WHERE ");
+    /// query_builder
+    ///     .push_named("a = :min AND b < :max OR c = :min", &arguments)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(query_builder.sql(), "SELECT * FROM t WHERE a = $1 AND b < $2 OR c = $1");
+    /// # }
+    /// ```
+    pub fn push_named<A>(&mut self, sql: impl Display, arguments: &A) -> Result<&mut Self, Error>
+    where
+        A: NamedArguments<'args, DB> + ?Sized,
+    {
+        named::push_named(self, &sql.to_string(), arguments)?;
+
+        Ok(self)
+    }
+
+    /// Append another fragment to this one.
+    pub fn push_fragment(&mut self, fragment: Fragment<'args, DB>) -> &mut Self {
+        let offset = self.binds.len();
+        for segment in fragment.segments {
+            match segment {
+                Segment::Sql(sql) => {
+                    self.push(sql);
+                }
+                Segment::Bind => self.segments.push(Segment::Bind),
+                Segment::Repeat(index) => self.segments.push(Segment::Repeat(offset + index)),
+            }
+        }
+        self.binds.extend(fragment.binds);
+
+        self
+    }
+
+    /// Whether this fragment contains neither SQL nor bind arguments.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// The number of bind arguments in this fragment.
+    pub fn bind_count(&self) -> usize {
+        self.binds.len()
+    }
+}
+
+impl<'args, DB: Database> From<Fragment<'args, DB>> for QueryBuilder<'args, DB> {
+    fn from(fragment: Fragment<'args, DB>) -> Self {
+        let mut query_builder = QueryBuilder::default();
+        query_builder.push_fragment(fragment);
+        query_builder
+    }
+}
+
+/// Something that can be spliced into a [`QueryBuilder`] with
+/// [`push_fragment()`][QueryBuilder::push_fragment].
+///
+/// This is implemented for [`Fragment`] (by value or by reference) and for [`QueryBuilder`]
+/// itself.
+pub trait PushFragment<'args, DB: Database>: Sized {
+    /// Append this fragment's SQL and bind arguments to `query_builder`.
+    ///
+    /// ### Panics
+    /// If [`.try_push_to()`][Self::try_push_to] returns an error.
+    fn push_to(self, query_builder: &mut QueryBuilder<'args, DB>) {
+        if let Err(error) = self.try_push_to(query_builder) {
+            panic!("{error}");
+        }
+    }
+
+    /// Append this fragment's SQL and bind arguments to `query_builder`, or return an error and
+    /// leave `query_builder` unchanged if they can't be pushed.
+    fn try_push_to(self, query_builder: &mut QueryBuilder<'args, DB>) -> Result<(), Error>;
+
+    /// Whether this fragment contains neither SQL nor bind arguments, in which case it is skipped
+    /// by [`WhereClause`][crate::builder2::WhereClause].
+    fn is_empty(&self) -> bool;
+}
+
+impl<'args, DB: Database> PushFragment<'args, DB> for Fragment<'args, DB> {
+    fn try_push_to(self, query_builder: &mut QueryBuilder<'args, DB>) -> Result<(), Error> {
+        query_builder.check_not_built()?;
+
+        let mut binds = self.binds.iter();
+        // The placeholder number of each bind argument, once pushed.
+        let mut numbers = Vec::with_capacity(self.binds.len());
+        for segment in self.segments {
+            match segment {
+                Segment::Sql(sql) => {
+                    query_builder.push(sql);
+                }
+                Segment::Bind => {
+                    let bind = binds.next().expect("BUG: fewer binds than placeholders");
+                    query_builder.push_boxed_bind(bind.clone().boxed());
+                    numbers.push(query_builder.bind_count());
+                }
+                Segment::Repeat(index) => {
+                    query_builder.push_repeated_bind(&self.binds[index], numbers[index]);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        Fragment::is_empty(self)
+    }
+}
+
+impl<'args, DB: Database> PushFragment<'args, DB> for &Fragment<'args, DB> {
+    fn try_push_to(self, query_builder: &mut QueryBuilder<'args, DB>) -> Result<(), Error> {
+        self.clone().try_push_to(query_builder)
+    }
+
+    fn is_empty(&self) -> bool {
+        Fragment::is_empty(self)
+    }
+}
@@ -0,0 +1,573 @@
+//! Keyset ("seek") pagination, with opaque cursor tokens.
+
+use std::fmt::Display;
+
+use sqlx::database::Database;
+use sqlx::encode::Encode;
+use sqlx::types::Type;
+
+use crate::builder2::QueryBuilder;
+use crate::dialect::Dialect;
+use crate::error::Error;
+use crate::fragment::Fragment;
+
+/// The direction of a sort column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Ascending order, the default.
+    #[default]
+    Asc,
+    /// Descending order.
+    Desc,
+}
+
+impl Direction {
+    /// The SQL keyword for this direction, `ASC` or `DESC`.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Direction::Asc => "ASC",
+            Direction::Desc => "DESC",
+        }
+    }
+
+    /// The comparison operator matching the rows that come after a given row.
+    fn seek_operator(self) -> &'static str {
+        match self {
+            Direction::Asc => " > ",
+            Direction::Desc => " < ",
+        }
+    }
+}
+
+/// A value of a [`Cursor`].
+///
+/// Values are bound as `BIGINT`, `DOUBLE PRECISION`, `TEXT` or `BOOLEAN`, depending on the
+/// variant. Columns of other types, such as timestamps, can either be given a cast with
+/// [`Keyset::column_as()`] or be stored in a cursor in a form the database compares the same
+/// way, such as an integer epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CursorValue {
+    /// An integer.
+    Int(i64),
+    /// A floating-point number.
+    Float(f64),
+    /// A string.
+    Text(String),
+    /// A boolean.
+    Bool(bool),
+}
+
+macro_rules! impl_from_for_cursor_value {
+    ($variant:ident: $($T:ty),+) => {
+        $(
+            impl From<$T> for CursorValue {
+                fn from(value: $T) -> Self {
+                    CursorValue::$variant(value.into())
+                }
+            }
+        )+
+    };
+}
+
+impl_from_for_cursor_value!(Int: i8, i16, i32, i64, u8, u16, u32);
+impl_from_for_cursor_value!(Float: f32, f64);
+impl_from_for_cursor_value!(Text: String, &str);
+impl_from_for_cursor_value!(Bool: bool);
+
+/// The sort key of the last row of a page, from which the next page starts.
+///
+/// A cursor is usually taken from the last row returned, handed to the client as an opaque
+/// token with [`.to_token()`][Self::to_token], and read back from the next request with
+/// [`Cursor::from_token()`]. The token is not encrypted nor signed: it only hides the shape of
+/// the cursor, and clients can forge one with any values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cursor(Vec<CursorValue>);
+
+impl Cursor {
+    /// Make a cursor from the values of the sort columns, in the same order as the columns of
+    /// the [`Keyset`].
+    pub fn new<I>(values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<CursorValue>,
+    {
+        Cursor(values.into_iter().map(Into::into).collect())
+    }
+
+    /// The values of the cursor.
+    pub fn values(&self) -> &[CursorValue] {
+        &self.0
+    }
+
+    /// Encode the cursor as a URL-safe string.
+    pub fn to_token(&self) -> String {
+        let mut bytes = Vec::new();
+        for value in &self.0 {
+            match value {
+                CursorValue::Int(int) => bytes.extend(format!("i{int};").into_bytes()),
+                CursorValue::Float(float) => bytes.extend(format!("f{float};").into_bytes()),
+                CursorValue::Text(text) => {
+                    bytes.extend(format!("s{}:", text.len()).into_bytes());
+                    bytes.extend(text.as_bytes());
+                }
+                CursorValue::Bool(bool) => bytes.extend(if *bool { b"b1" } else { b"b0" }),
+            }
+        }
+
+        base64_encode(&bytes)
+    }
+
+    /// Decode a token made by [`.to_token()`][Self::to_token].
+    ///
+    /// ### Errors
+    /// Returns [`Error::InvalidCursor`] if `token` isn't a valid token.
+    pub fn from_token(token: &str) -> Result<Self, Error> {
+        let bytes = base64_decode(token).ok_or(Error::InvalidCursor)?;
+        let mut rest = std::str::from_utf8(&bytes).map_err(|_| Error::InvalidCursor)?;
+
+        let mut values = Vec::new();
+        while let Some(tag) = rest.chars().next() {
+            rest = &rest[tag.len_utf8()..];
+            let value = match tag {
+                'i' | 'f' => {
+                    let (number, tail) = rest.split_once(';').ok_or(Error::InvalidCursor)?;
+                    rest = tail;
+                    if tag == 'i' {
+                        CursorValue::Int(number.parse().map_err(|_| Error::InvalidCursor)?)
+                    } else {
+                        CursorValue::Float(number.parse().map_err(|_| Error::InvalidCursor)?)
+                    }
+                }
+                's' => {
+                    let (len, tail) = rest.split_once(':').ok_or(Error::InvalidCursor)?;
+                    let len: usize = len.parse().map_err(|_| Error::InvalidCursor)?;
+                    let text = tail.get(..len).ok_or(Error::InvalidCursor)?;
+                    rest = &tail[len..];
+                    CursorValue::Text(text.to_owned())
+                }
+                'b' => {
+                    let value = match rest.chars().next() {
+                        Some('0') => false,
+                        Some('1') => true,
+                        _ => return Err(Error::InvalidCursor),
+                    };
+                    rest = &rest[1..];
+                    CursorValue::Bool(value)
+                }
+                _ => return Err(Error::InvalidCursor),
+            };
+            values.push(value);
+        }
+
+        Ok(Cursor(values))
+    }
+}
+
+/// A column of a [`Keyset`].
+#[derive(Clone, Debug)]
+struct KeysetColumn {
+    column: String,
+    direction: Direction,
+    cast: Option<String>,
+}
+
+/// The sort order of a paginated query, and the position to resume it from.
+///
+/// Keyset pagination filters out the rows up to the last one of the previous page, instead of
+/// skipping them with `OFFSET`, so that each page can be read from an index. The columns must
+/// form a unique key, e.g. by ending with the primary key, and must not be nullable.
+///
+/// [`.predicate()`][Self::predicate] returns the filter, and
+/// [`.push_order_by()`][Self::push_order_by] pushes the matching `ORDER BY` clause. When every
+/// column has the same direction, the filter is a row comparison such as `(a, b) > ($1, $2)`;
+/// otherwise, or with backends that don't support row comparisons, it is expanded to
+/// `(a > $1 OR (a = $1 AND b < $2))`.
+///
+/// ```rust
+/// # #[cfg(feature = "postgres")] {
+/// use sqlx::Postgres;
+/// use sqlx_fragment::builder2::QueryBuilder;
+/// use sqlx_fragment::{Cursor, CursorValue, Direction, Keyset};
+///
+/// // The token would come from the previous response.
+/// let token = Cursor::new([42]).to_token();
+/// let cursor = Cursor::from_token(&token).unwrap();
+///
+/// let keyset = Keyset::new().column("id", Direction::Asc).after(cursor).unwrap();
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users");
+/// query_builder.where_clause().push_fragment(keyset.predicate());
+/// keyset.push_order_by(&mut query_builder);
+/// query_builder.push(" LIMIT 20");
+///
+/// assert_eq!(
+///     query_builder.sql(),
+///     "SELECT * FROM users WHERE id > $1 ORDER BY id ASC LIMIT 20"
+/// );
+///
+/// let keyset = Keyset::new()
+///     .column_as("created_at", Direction::Desc, "TIMESTAMPTZ")
+///     .column("id", Direction::Asc)
+///     .after(Cursor::new([CursorValue::from("2024-01-01T00:00:00Z"), CursorValue::from(42)]))
+///     .unwrap();
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM posts");
+/// query_builder.where_clause().push_fragment(keyset.predicate());
+/// keyset.push_order_by(&mut query_builder);
+///
+/// assert_eq!(
+///     query_builder.sql(),
+///     "SELECT * FROM posts WHERE (created_at < CAST($1 AS TIMESTAMPTZ) OR \
+///      (created_at = CAST($1 AS TIMESTAMPTZ) AND id > $2)) ORDER BY created_at DESC, id ASC"
+/// );
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Keyset {
+    columns: Vec<KeysetColumn>,
+    after: Option<Cursor>,
+}
+
+impl Keyset {
+    /// Start a keyset with no columns, on the first page.
+    pub fn new() -> Self {
+        Keyset::default()
+    }
+
+    /// Add a sort column.
+    ///
+    /// `column` is pushed as-is, like [`QueryBuilder::push()`], and may be any expression.
+    ///
+    /// ### Panics
+    /// If called after [`.after()`][Self::after], as the cursor would be missing a value.
+    pub fn column(self, column: impl Display, direction: Direction) -> Self {
+        self.push_column(KeysetColumn {
+            column: column.to_string(),
+            direction,
+            cast: None,
+        })
+    }
+
+    /// Add a sort column whose cursor value is cast to `sql_type`, as in
+    /// `CAST($1 AS TIMESTAMPTZ)`.
+    ///
+    /// This lets a column of any type be compared to a [`CursorValue`], typically
+    /// [`CursorValue::Text`].
+    ///
+    /// ### Panics
+    /// If called after [`.after()`][Self::after], as the cursor would be missing a value.
+    pub fn column_as(
+        self,
+        column: impl Display,
+        direction: Direction,
+        sql_type: impl Display,
+    ) -> Self {
+        self.push_column(KeysetColumn {
+            column: column.to_string(),
+            direction,
+            cast: Some(sql_type.to_string()),
+        })
+    }
+
+    fn push_column(mut self, column: KeysetColumn) -> Self {
+        assert!(
+            self.after.is_none(),
+            "`Keyset::after()` must be called after every column is added"
+        );
+        self.columns.push(column);
+        self
+    }
+
+    /// Resume after the row whose sort key is `cursor`.
+    ///
+    /// Every column must be added before calling this method.
+    ///
+    /// ### Errors
+    /// Returns [`Error::InvalidCursor`] if the cursor doesn't have one value per column, e.g.
+    /// because a client sent a token from a query with a different sort order, or if it is
+    /// empty.
+    pub fn after(mut self, cursor: Cursor) -> Result<Self, Error> {
+        if cursor.0.is_empty() || cursor.0.len() != self.columns.len() {
+            return Err(Error::InvalidCursor);
+        }
+
+        self.after = Some(cursor);
+        Ok(self)
+    }
+
+    /// The condition matching the rows after the cursor, or an empty fragment on the first
+    /// page.
+    ///
+    /// An empty fragment is skipped by [`WhereClause`][crate::builder2::WhereClause] and
+    /// [`Cond`][crate::Cond].
+    pub fn predicate<'args, DB>(&self) -> Fragment<'args, DB>
+    where
+        DB: Database,
+        i64: Encode<'args, DB> + Type<DB>,
+        f64: Encode<'args, DB> + Type<DB>,
+        String: Encode<'args, DB> + Type<DB>,
+        bool: Encode<'args, DB> + Type<DB>,
+    {
+        let mut fragment = Fragment::default();
+        let (Some(cursor), Some(first)) = (&self.after, self.columns.first()) else {
+            return fragment;
+        };
+        assert_eq!(
+            cursor.0.len(),
+            self.columns.len(),
+            "BUG: the cursor doesn't have one value per column"
+        );
+
+        let direction = first.direction;
+        let uniform = self
+            .columns
+            .iter()
+            .all(|column| column.direction == direction);
+
+        if self.columns.len() == 1 {
+            fragment.push(&first.column);
+            fragment.push(direction.seek_operator());
+            push_value(&mut fragment, first, &cursor.0[0], None);
+        } else if uniform && Dialect::of::<DB>().has_row_comparison() {
+            fragment.push("(");
+            for (i, column) in self.columns.iter().enumerate() {
+                if i > 0 {
+                    fragment.push(", ");
+                }
+                fragment.push(&column.column);
+            }
+            fragment.push(")");
+            fragment.push(direction.seek_operator());
+            fragment.push("(");
+            for (i, (column, value)) in self.columns.iter().zip(&cursor.0).enumerate() {
+                if i > 0 {
+                    fragment.push(", ");
+                }
+                push_value(&mut fragment, column, value, None);
+            }
+            fragment.push(")");
+        } else {
+            // Each value is bound once, then repeated with numbered placeholders.
+            let mut indices = vec![None; self.columns.len()];
+
+            fragment.push("(");
+            for i in 0..self.columns.len() {
+                if i > 0 {
+                    fragment.push(" OR (");
+                }
+                let keys = self.columns.iter().zip(&cursor.0).zip(&mut indices);
+                for (j, ((column, value), index)) in keys.enumerate().take(i + 1) {
+                    if j > 0 {
+                        fragment.push(" AND ");
+                    }
+                    fragment.push(&column.column);
+                    fragment.push(if j < i {
+                        " = "
+                    } else {
+                        column.direction.seek_operator()
+                    });
+                    *index = Some(push_value(&mut fragment, column, value, *index));
+                }
+                if i > 0 {
+                    fragment.push(")");
+                }
+            }
+            fragment.push(")");
+        }
+
+        fragment
+    }
+
+    /// Push ` ORDER BY a ASC, b DESC` with the columns of the keyset.
+    ///
+    /// Nothing is pushed if the keyset has no columns.
+    pub fn push_order_by<'args, DB: Database>(&self, query_builder: &mut QueryBuilder<'args, DB>) {
+        for (i, column) in self.columns.iter().enumerate() {
+            query_builder.push(if i == 0 { " ORDER BY " } else { ", " });
+            query_builder.push(&column.column);
+            query_builder.push(" ");
+            query_builder.push(column.direction.keyword());
+        }
+    }
+}
+
+/// Push `value` as a bind argument, or repeat the bind argument at `index` if it was already
+/// pushed, and return the index of the bind argument.
+fn push_value<'args, DB>(
+    fragment: &mut Fragment<'args, DB>,
+    column: &KeysetColumn,
+    value: &CursorValue,
+    index: Option<usize>,
+) -> usize
+where
+    DB: Database,
+    i64: Encode<'args, DB> + Type<DB>,
+    f64: Encode<'args, DB> + Type<DB>,
+    String: Encode<'args, DB> + Type<DB>,
+    bool: Encode<'args, DB> + Type<DB>,
+{
+    if column.cast.is_some() {
+        fragment.push("CAST(");
+    }
+
+    let index = match index {
+        Some(index) => {
+            fragment.push_repeat(index);
+            index
+        }
+        None => {
+            match value.clone() {
+                CursorValue::Int(int) => fragment.push_bind(int),
+                CursorValue::Float(float) => fragment.push_bind(float),
+                CursorValue::Text(text) => fragment.push_bind(text),
+                CursorValue::Bool(bool) => fragment.push_bind(bool),
+            };
+            fragment.bind_count() - 1
+        }
+    };
+
+    if let Some(cast) = &column.cast {
+        fragment.push(format_args!(" AS {cast})"));
+    }
+
+    index
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encode `bytes` as unpadded URL-safe base64.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | u32::from(byte) << (16 - 8 * i)
+        });
+        for i in 0..=chunk.len() {
+            encoded.push(BASE64_ALPHABET[(group >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+/// Decode unpadded URL-safe base64, or return `None` if `encoded` isn't valid.
+fn base64_decode(encoded: &str) -> Option<Vec<u8>> {
+    if encoded.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(encoded.len() / 4 * 3 + 2);
+    for chunk in encoded.as_bytes().chunks(4) {
+        let mut group = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let digit = BASE64_ALPHABET.iter().position(|&d| d == c)? as u32;
+            group |= digit << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            bytes.push((group >> (16 - 8 * i)) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64_decode, base64_encode, Cursor, CursorValue, Keyset};
+    use crate::error::Error;
+
+    #[test]
+    fn base64_round_trip() {
+        for len in 0..=16 {
+            let bytes: Vec<u8> = (0..len).map(|i| (i * 37 + 200) as u8).collect();
+            let encoded = base64_encode(&bytes);
+            assert!(encoded
+                .bytes()
+                .all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_'));
+            assert_eq!(base64_decode(&encoded), Some(bytes));
+        }
+
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(b"\xfb\xff"), "-_8");
+    }
+
+    #[test]
+    fn base64_rejects_invalid_input() {
+        assert_eq!(base64_decode("A"), None);
+        assert_eq!(base64_decode("Zm9vY"), None);
+        assert_eq!(base64_decode("Zm9v+mFy"), None);
+        assert_eq!(base64_decode("Zm9v=="), None);
+        assert_eq!(base64_decode("Zm9vYmFy\n"), None);
+    }
+
+    #[test]
+    fn token_round_trip() {
+        let cursor = Cursor::new([
+            CursorValue::Int(-42),
+            CursorValue::Float(1.5),
+            CursorValue::Text("héllo; wörld: 🦀".to_owned()),
+            CursorValue::Text(String::new()),
+            CursorValue::Bool(true),
+            CursorValue::Bool(false),
+            CursorValue::Int(i64::MIN),
+        ]);
+
+        assert_eq!(Cursor::from_token(&cursor.to_token()).unwrap(), cursor);
+    }
+
+    #[test]
+    fn truncated_and_garbage_tokens() {
+        let token =
+            Cursor::new([CursorValue::Text("héllo".to_owned()), CursorValue::Int(7)]).to_token();
+        for len in 1..token.len() {
+            let truncated = &token[..len];
+            if let Ok(cursor) = Cursor::from_token(truncated) {
+                // Cutting right after a value gives a shorter, valid cursor.
+                assert!(cursor.values().len() < 2, "{truncated}");
+            }
+        }
+
+        for garbage in ["!!!!", "A", "eA", "aTQy", "czk6YWJj", "aTE7eA", "Yg", "Yjk"] {
+            assert!(
+                matches!(Cursor::from_token(garbage), Err(Error::InvalidCursor)),
+                "{garbage}"
+            );
+        }
+        // Not UTF-8.
+        assert!(matches!(
+            Cursor::from_token(&base64_encode(b"s1:\xff")),
+            Err(Error::InvalidCursor)
+        ));
+        // Cut in the middle of a multibyte character.
+        assert!(matches!(
+            Cursor::from_token(&base64_encode("s1:é".as_bytes())),
+            Err(Error::InvalidCursor)
+        ));
+    }
+
+    #[test]
+    fn empty_cursors_are_rejected() {
+        let empty = Cursor::from_token("").unwrap();
+        assert!(empty.values().is_empty());
+
+        assert!(matches!(
+            Keyset::new().after(empty.clone()),
+            Err(Error::InvalidCursor)
+        ));
+        assert!(matches!(
+            Keyset::new()
+                .column("id", super::Direction::Asc)
+                .after(empty),
+            Err(Error::InvalidCursor)
+        ));
+    }
+
+    #[test]
+    #[should_panic = "`Keyset::after()` must be called after every column is added"]
+    fn columns_cannot_be_added_after_the_cursor() {
+        let _ = Keyset::new()
+            .column("a", super::Direction::Asc)
+            .after(Cursor::new([1]))
+            .unwrap()
+            .column("b", super::Direction::Asc);
+    }
+}
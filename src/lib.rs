@@ -2,17 +2,244 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! API for pushing formatted chunks of SQL to an SQLX QueryBuilder
+//!
+//! ### Interoperability with `sqlx::QueryBuilder`
+//! A [`builder2::QueryBuilder`] can be converted to a `sqlx::QueryBuilder` with `TryFrom`, which
+//! fails with [`Error::Encode`] if a bind argument can't be encoded. The reverse conversion,
+//! with `From`, is only implemented for Postgres and MySQL: SQLite arguments borrow from the
+//! `sqlx::QueryBuilder` and can't be taken out of it.
 
-mod builder2;
+mod arguments;
+pub mod builder2;
+#[cfg(doctest)]
+mod compile_fail;
+mod cond;
+mod debug;
+mod dialect;
+mod error;
+mod filter;
+mod fragment;
+mod keyset;
+mod named;
+mod raw;
+mod sort;
+mod strict;
+mod tuple;
+#[cfg(feature = "postgres")]
+mod unnest;
 
-use sqlx::{Database, QueryBuilder};
+use sqlx_fragment_lexer as lexer;
 
-fn push_fragment<'args, DB: Database>(
-    query: &mut QueryBuilder<'args, DB>,
-    fragment: QueryBuilder<'args, DB>,
-) {
-    //let fragment = fragment.build();
-    //fragment.take_arguments().unwrap();
+pub use cond::Cond;
+pub use debug::DebugSql;
+pub use error::Error;
+pub use filter::Filter;
+pub use fragment::{Fragment, PushFragment};
+pub use keyset::{Cursor, CursorValue, Direction, Keyset};
+pub use named::NamedArguments;
+pub use raw::{RawSql, TrustedSql};
+pub use sort::{Nulls, Sort, SortColumns, SortKey};
+pub use strict::{StrictFragment, StrictQueryBuilder};
+pub use tuple::BindTuple;
+#[cfg(feature = "postgres")]
+pub use unnest::UnnestRow;
 
-    //query.push_sql(fragment);
+/// Build a [`Fragment`] from a format string.
+///
+/// Placeholders are written with the same syntax as [`format!()`], and may refer to named
+/// arguments, positional arguments or variables in scope. What gets pushed depends on the
+/// placeholder:
+///
+/// * `{x}` pushes `x` as a bind argument, with [`Fragment::push_bind()`].
+/// * `{x:frag}` splices the fragment `x`, with [`Fragment::push_fragment()`].
+/// * `{x:raw}` pushes `x` as raw SQL, with [`Fragment::push()`]. `x` must implement [`RawSql`].
+///
+/// Use `{{` and `}}` for literal braces.
+///
+/// A bind argument used by several placeholders is moved into the fragment once, so it doesn't
+/// need to be `Copy`: Postgres reuses the same `$N`, other backends bind it again. Fragments and raw SQL can only
+/// be used by one placeholder.
+///
+/// ### Compile-time checks
+/// The SQL text of the format string is checked at compile time, without a database
+/// connection, using the lexical rules of the dialect selected with the `postgres`, `mysql` or
+/// `sqlite` feature. The macro rejects:
+///
+/// * unbalanced parentheses;
+/// * unterminated string literals, quoted identifiers, comments and dollar-quoted strings;
+/// * placeholders inside string literals or comments, which would not be bound;
+/// * literal `$N` (Postgres, SQLite) or `?` (MySQL, SQLite) placeholders, which would collide
+///   with the ones written for bind arguments. `?` is allowed when the `postgres` feature is
+///   enabled, as it is also a JSON operator there.
+///
+/// ```rust
+/// # #[cfg(feature = "postgres")] {
+/// use sqlx::Postgres;
+/// use sqlx_fragment::builder2::QueryBuilder;
+/// use sqlx_fragment::fragment;
+///
+/// let id = 42;
+/// let other = fragment!("age > {}", 18);
+/// let col = "name";
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("");
+/// query_builder.push_fragment(fragment!(
+///     "SELECT * FROM users WHERE id = {id} AND {other:frag} ORDER BY {col:raw}"
+/// ));
+///
+/// assert_eq!(
+///     query_builder.sql(),
+///     "SELECT * FROM users WHERE id = $1 AND age > $2 ORDER BY name"
+/// );
+///
+/// let name = String::from("alice");
+/// let fragment: sqlx_fragment::Fragment<Postgres> =
+///     fragment!("first_name = {0} OR last_name = {0}", name);
+///
+/// assert_eq!(fragment.bind_count(), 1);
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("");
+/// query_builder.push_fragment(fragment);
+/// assert_eq!(query_builder.sql(), "first_name = $1 OR last_name = $1");
+/// # }
+/// # #[cfg(feature = "mysql")] {
+/// # let name = String::from("alice");
+/// # let mut query_builder: sqlx_fragment::builder2::QueryBuilder<sqlx::MySql> =
+/// #     sqlx_fragment::builder2::QueryBuilder::new("");
+/// # query_builder.push_fragment(sqlx_fragment::fragment!("a = {0} OR b = {0} OR c = {1}", name, 1));
+/// # assert_eq!(query_builder.sql(), "a = ? OR b = ? OR c = ?");
+/// # assert_eq!(query_builder.to_debug_sql().to_string(), "a = 'alice' OR b = 'alice' OR c = 1");
+/// # }
+/// ```
+pub use sqlx_fragment_macros::fragment;
+
+/// Derive [`NamedArguments`] for a struct with named fields.
+///
+/// Every field is an argument named after the field, and must satisfy the same bounds as
+/// [`Fragment::push_bind()`]. Use `#[named(rename = "other_name")]` to change the name of an
+/// argument, and `#[named(skip)]` to leave a field out.
+///
+/// ```rust
+/// # #[cfg(feature = "postgres")] {
+/// use sqlx::Postgres;
+/// use sqlx_fragment::builder2::QueryBuilder;
+/// use sqlx_fragment::NamedArguments;
+///
+/// #[derive(NamedArguments)]
+/// struct Range {
+///     min: i32,
+///     #[named(rename = "max")]
+///     upper_bound: i32,
+/// }
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM t WHERE ");
+/// query_builder
+///     .push_named("a >= :min AND a < :max", &Range { min: 1, upper_bound: 10 })
+///     .unwrap();
+///
+/// assert_eq!(query_builder.sql(), "SELECT * FROM t WHERE a >= $1 AND a < $2");
+/// # }
+/// ```
+pub use sqlx_fragment_macros::NamedArguments;
+
+/// Derive [`Filter`] for a struct with named fields.
+///
+/// Every field is a condition on the column named after the field, written as
+/// `column = $1`. A field of type `Option<T>` is skipped when it is `None`, and only binds the
+/// inner value otherwise. A field of type `Vec<T>` or `Option<Vec<T>>` is written as
+/// `column IN ($1, $2, ...)`, or `FALSE` if the vector is empty. Bound values must satisfy the
+/// same bounds as [`Fragment::push_bind()`].
+///
+/// Fields can be customized with `#[filter(...)]`:
+///
+/// * `column = "users.age"` sets the column, which may be any SQL expression;
+/// * `op = ">="` sets the comparison operator, which may be `IN` or `NOT IN` for vectors, and
+///   anything the database accepts between a column and a value otherwise, such as `LIKE` or
+///   `@>`. A vector compared with another operator is bound as a single value;
+/// * `skip` leaves the field out.
+///
+/// Types are recognized by name, so a type alias of `Option` or `Vec` isn't.
+///
+/// ```rust
+/// # #[cfg(feature = "postgres")] {
+/// use sqlx::Postgres;
+/// use sqlx_fragment::builder2::QueryBuilder;
+/// use sqlx_fragment::Filter;
+///
+/// #[derive(Filter)]
+/// struct UserFilter {
+///     name: Option<String>,
+///     #[filter(column = "age", op = ">=")]
+///     min_age: Option<i32>,
+///     #[filter(column = "id")]
+///     ids: Option<Vec<i64>>,
+///     #[filter(skip)]
+///     page: u32,
+/// }
+///
+/// let filter = UserFilter {
+///     name: None,
+///     min_age: Some(18),
+///     ids: Some(vec![1, 2, 3]),
+///     page: 0,
+/// };
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users");
+/// query_builder.where_clause().push_filter(&filter).push("deleted_at IS NULL");
+///
+/// assert_eq!(
+///     query_builder.sql(),
+///     "SELECT * FROM users WHERE age >= $1 AND id IN ($2, $3, $4) AND deleted_at IS NULL"
+/// );
+/// # }
+/// ```
+pub use sqlx_fragment_macros::Filter;
+
+/// Derive [`SortKey`] for an enum whose variants have no fields.
+///
+/// Every variant is a key named after the variant in `snake_case`, sorting by the column of the
+/// same name. Use `#[sort(rename = "other_key")]` to change the key, and
+/// `#[sort(column = "table.column")]` to change the column.
+///
+/// ```rust
+/// # #[cfg(feature = "postgres")] {
+/// use sqlx::Postgres;
+/// use sqlx_fragment::builder2::QueryBuilder;
+/// use sqlx_fragment::{Sort, SortKey};
+///
+/// #[derive(SortKey)]
+/// enum PostSort {
+///     CreatedAt,
+///     #[sort(rename = "author", column = "users.name")]
+///     AuthorName,
+/// }
+///
+/// let sort = Sort::parse_keys::<PostSort>("-created_at,author").unwrap();
+///
+/// let mut query_builder: QueryBuilder<Postgres> =
+///     QueryBuilder::new("SELECT posts.* FROM posts JOIN users ON users.id = posts.author_id");
+/// sort.push_order_by(&mut query_builder);
+///
+/// assert!(query_builder.sql().ends_with(" ORDER BY created_at DESC, users.name ASC"));
+/// # }
+/// ```
+pub use sqlx_fragment_macros::SortKey;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use sqlx;
+
+    use sqlx::Database;
+
+    use crate::{Fragment, RawSql};
+
+    /// Used by `fragment!()` to check that `{x:raw}` values implement [`RawSql`].
+    pub fn raw_sql<T: RawSql>(sql: T) -> T {
+        sql
+    }
+
+    /// Used by `fragment!()` for a value used by several placeholders.
+    pub fn push_repeat<DB: Database>(fragment: &mut Fragment<'_, DB>, index: usize) {
+        fragment.push_repeat(index);
+    }
 }
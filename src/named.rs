@@ -0,0 +1,196 @@
+//! Named parameters, e.g. `WHERE a = :min`, bound from a map or a struct.
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hash};
+
+use sqlx::database::Database;
+use sqlx::encode::Encode;
+use sqlx::types::Type;
+
+use crate::dialect::Dialect;
+use crate::error::Error;
+use crate::fragment::Fragment;
+use crate::lexer::{Lexer, TokenKind};
+
+/// A set of bind arguments that can be looked up by name.
+///
+/// This is implemented for `HashMap` and `BTreeMap` with string keys, and can be derived for
+/// structs with `#[derive(NamedArguments)]`, in which case every field is an argument named
+/// after the field. Fields can be renamed with `#[named(rename = "other_name")]` and left out
+/// with `#[named(skip)]`.
+///
+/// See [`Fragment::push_named()`] for details.
+pub trait NamedArguments<'args, DB: Database> {
+    /// Push the argument called `name` to `fragment` with [`Fragment::push_bind()`].
+    ///
+    /// Returns `false`, and doesn't push anything, if there is no such argument.
+    fn push_named_bind(&self, name: &str, fragment: &mut Fragment<'args, DB>) -> bool;
+
+    /// The names of all the arguments.
+    fn names(&self) -> Vec<&str>;
+}
+
+impl<'args, DB, K, V, S> NamedArguments<'args, DB> for HashMap<K, V, S>
+where
+    DB: Database,
+    K: Borrow<str> + Eq + Hash,
+    V: 'args + Send + Sync + Clone + Debug + Encode<'args, DB> + Type<DB>,
+    S: BuildHasher,
+{
+    fn push_named_bind(&self, name: &str, fragment: &mut Fragment<'args, DB>) -> bool {
+        match self.get(name) {
+            Some(value) => {
+                fragment.push_bind(value.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn names(&self) -> Vec<&str> {
+        self.keys().map(Borrow::borrow).collect()
+    }
+}
+
+impl<'args, DB, K, V> NamedArguments<'args, DB> for BTreeMap<K, V>
+where
+    DB: Database,
+    K: Borrow<str> + Ord,
+    V: 'args + Send + Sync + Clone + Debug + Encode<'args, DB> + Type<DB>,
+{
+    fn push_named_bind(&self, name: &str, fragment: &mut Fragment<'args, DB>) -> bool {
+        match self.get(name) {
+            Some(value) => {
+                fragment.push_bind(value.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn names(&self) -> Vec<&str> {
+        self.keys().map(Borrow::borrow).collect()
+    }
+}
+
+/// Append `sql` to `fragment`, binding every `:name` parameter to the matching argument.
+pub(crate) fn push_named<'args, DB, A>(
+    fragment: &mut Fragment<'args, DB>,
+    sql: &str,
+    arguments: &A,
+) -> Result<(), Error>
+where
+    DB: Database,
+    A: NamedArguments<'args, DB> + ?Sized,
+{
+    // Work on a copy so that `fragment` is left untouched on error.
+    let mut named = Fragment::default();
+    // Name and index in `named` of every argument bound so far.
+    let mut bound: Vec<(&str, usize)> = Vec::new();
+
+    for token in Lexer::new(sql, Dialect::of::<DB>().syntax()) {
+        if token.kind != TokenKind::Named {
+            named.push(token.text);
+            continue;
+        }
+
+        let name = &token.text[1..];
+        match bound.iter().find(|(bound_name, _)| *bound_name == name) {
+            Some(&(_, index)) => {
+                named.push_repeat(index);
+            }
+            None => {
+                let index = named.bind_count();
+                if !arguments.push_named_bind(name, &mut named) {
+                    return Err(Error::MissingNamedArgument(name.to_owned()));
+                }
+                assert_eq!(
+                    named.bind_count(),
+                    index + 1,
+                    "`NamedArguments::push_named_bind()` must push exactly one bind argument"
+                );
+                bound.push((name, index));
+            }
+        }
+    }
+
+    if let Some(unused) = arguments
+        .names()
+        .into_iter()
+        .find(|name| !bound.iter().any(|(bound_name, _)| bound_name == name))
+    {
+        return Err(Error::UnusedNamedArgument(unused.to_owned()));
+    }
+
+    fragment.push_fragment(named);
+    Ok(())
+}
+
+#[cfg(all(test, any(feature = "postgres", feature = "mysql")))]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::builder2::QueryBuilder;
+    #[cfg(feature = "postgres")]
+    use crate::error::Error;
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn mysql_comments_and_escaped_quotes_are_skipped() {
+        let arguments = HashMap::from([("a", 1)]);
+
+        let mut query_builder: QueryBuilder<sqlx::MySql> = QueryBuilder::new("");
+        query_builder
+            .push_named(
+                r"SELECT 1 # :foo
+ WHERE a = :a AND b = 'it\'s :bar' AND c = :a",
+                &arguments,
+            )
+            .unwrap();
+
+        assert_eq!(
+            query_builder.sql(),
+            r"SELECT 1 # :foo
+ WHERE a = ? AND b = 'it\'s :bar' AND c = ?"
+        );
+        assert_eq!(query_builder.bind_count(), 2);
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn postgres_literals_and_casts_are_skipped() {
+        let arguments = HashMap::from([("a", 1)]);
+
+        let mut query_builder: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("");
+        query_builder
+            .push_named(
+                "SELECT ':x', $$ :y $$, /* :z /* */ :w */ :a::INT4, E'\\' :v' -- :u",
+                &arguments,
+            )
+            .unwrap();
+
+        assert_eq!(
+            query_builder.sql(),
+            "SELECT ':x', $$ :y $$, /* :z /* */ :w */ $1::INT4, E'\\' :v' -- :u"
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn missing_and_unused_arguments() {
+        let arguments = HashMap::from([("a", 1)]);
+
+        let mut query_builder: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("");
+        assert!(matches!(
+            query_builder.push_named(":a + :b", &arguments),
+            Err(Error::MissingNamedArgument(name)) if name == "b"
+        ));
+        assert!(matches!(
+            query_builder.push_named("1", &arguments),
+            Err(Error::UnusedNamedArgument(name)) if name == "a"
+        ));
+        assert_eq!(query_builder.sql(), "");
+    }
+}
@@ -0,0 +1,55 @@
+//! Marker trait for SQL that can be pushed without escaping.
+
+use std::fmt::{self, Display};
+
+/// Marker trait for values that are trusted to be pushed to a query as raw SQL.
+///
+/// This is what the `{x:raw}` placeholders of [`fragment!()`][crate::fragment!] accept, and
+/// what [`StrictQueryBuilder::push()`][crate::StrictQueryBuilder::push] accepts. It is only
+/// implemented for values that can't come from user input, such as string literals, and for
+/// [`TrustedSql`].
+pub trait RawSql: Display {}
+
+impl RawSql for &'static str {}
+
+impl<T: Display> RawSql for TrustedSql<T> {}
+
+/// SQL text that is explicitly asserted to be safe to push as raw SQL.
+///
+/// Wrapping a value with [`TrustedSql::assume_safe()`] is the escape hatch for pushing
+/// SQL that is built at runtime to a [`StrictQueryBuilder`][crate::StrictQueryBuilder]. It should only be used
+/// with values that can't contain untrusted input; every call is a place to look at during a
+/// security review.
+///
+/// ```rust
+/// use sqlx_fragment::TrustedSql;
+///
+/// let direction = if true { "ASC" } else { "DESC" };
+/// let order_by = TrustedSql::assume_safe(format!("ORDER BY id {direction}"));
+/// assert_eq!(order_by.to_string(), "ORDER BY id ASC");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TrustedSql<T>(T);
+
+impl<T: Display> TrustedSql<T> {
+    /// Mark `sql` as trusted raw SQL.
+    ///
+    /// ### Warning
+    /// This bypasses the protection offered by
+    /// [`StrictQueryBuilder`][crate::StrictQueryBuilder]. Make sure `sql` can't be influenced by
+    /// user input; see [`QueryBuilder::push()`][crate::builder2::QueryBuilder::push].
+    pub fn assume_safe(sql: T) -> Self {
+        TrustedSql(sql)
+    }
+
+    /// Unwrap the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Display> Display for TrustedSql<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -0,0 +1,240 @@
+//! `ORDER BY` clauses parsed from user input, such as `sort=-created_at,name`.
+
+use std::marker::PhantomData;
+
+use sqlx::database::Database;
+
+use crate::builder2::QueryBuilder;
+use crate::dialect::Dialect;
+use crate::error::Error;
+use crate::keyset::Direction;
+
+/// A whitelist of the columns a [`Sort`] may use, mapping user-facing keys to SQL columns.
+///
+/// This is implemented for slices and arrays of `&'static str`, where each key is also the
+/// column, and of `(key, column)` pairs. Only the columns are ever pushed to the query, so they
+/// are required to be `&'static str`.
+pub trait SortColumns {
+    /// The column for the key `key`, or `None` if sorting by `key` isn't allowed.
+    fn sort_column(&self, key: &str) -> Option<&'static str>;
+}
+
+impl SortColumns for [&'static str] {
+    fn sort_column(&self, key: &str) -> Option<&'static str> {
+        self.iter().copied().find(|&column| column == key)
+    }
+}
+
+impl<const N: usize> SortColumns for [&'static str; N] {
+    fn sort_column(&self, key: &str) -> Option<&'static str> {
+        self.as_slice().sort_column(key)
+    }
+}
+
+impl SortColumns for [(&str, &'static str)] {
+    fn sort_column(&self, key: &str) -> Option<&'static str> {
+        self.iter()
+            .find(|(name, _)| *name == key)
+            .map(|&(_, column)| column)
+    }
+}
+
+impl<const N: usize> SortColumns for [(&str, &'static str); N] {
+    fn sort_column(&self, key: &str) -> Option<&'static str> {
+        self.as_slice().sort_column(key)
+    }
+}
+
+/// An enum of the keys a [`Sort`] may use, each mapped to a SQL column.
+///
+/// This is usually derived with `#[derive(SortKey)]`, for enums whose variants have no
+/// fields. See [`derive@SortKey`] and [`Sort::parse_keys()`].
+pub trait SortKey: Sized {
+    /// The variant for the key `key`, or `None` if there is none.
+    fn from_sort_key(key: &str) -> Option<Self>;
+
+    /// The column to sort by.
+    fn column(&self) -> &'static str;
+}
+
+/// Where `NULL` values go in a sorted column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Nulls {
+    /// Before every other value.
+    First,
+    /// After every other value.
+    Last,
+}
+
+/// A column of a [`Sort`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SortTerm {
+    column: &'static str,
+    direction: Direction,
+}
+
+/// A sort order parsed from user input, such as `-created_at,name`.
+///
+/// The input is a comma-separated list of keys, each sorted in ascending order, or in
+/// descending order if prefixed with `-`. Keys are looked up in a whitelist, either a
+/// [`SortColumns`] list or a [`SortKey`] enum, so only known columns ever make it to the query.
+///
+/// [`.nulls()`][Self::nulls] sets where `NULL` values go, and
+/// [`.tiebreaker()`][Self::tiebreaker] adds a unique column at the end of the order, so that
+/// rows with equal keys come in a deterministic order. Backends without `NULLS FIRST` and
+/// `NULLS LAST` get an equivalent `CASE WHEN column IS NULL ...` key instead.
+///
+/// ```rust
+/// # #[cfg(feature = "postgres")] {
+/// use sqlx::Postgres;
+/// use sqlx_fragment::builder2::QueryBuilder;
+/// use sqlx_fragment::{Direction, Error, Nulls, Sort, SortKey};
+///
+/// let sort = Sort::parse("-created_at,name", &["created_at", "name"])
+///     .unwrap()
+///     .nulls(Nulls::Last)
+///     .tiebreaker("id", Direction::Asc);
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users");
+/// sort.push_order_by(&mut query_builder);
+///
+/// assert_eq!(
+///     query_builder.sql(),
+///     "SELECT * FROM users ORDER BY created_at DESC NULLS LAST, name ASC NULLS LAST, id ASC"
+/// );
+///
+/// #[derive(SortKey)]
+/// enum UserSort {
+///     #[sort(column = "users.created_at")]
+///     CreatedAt,
+///     #[sort(rename = "name")]
+///     DisplayName,
+/// }
+///
+/// let sort = Sort::parse_keys::<UserSort>("name,-created_at").unwrap();
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users");
+/// sort.push_order_by(&mut query_builder);
+///
+/// assert_eq!(
+///     query_builder.sql(),
+///     "SELECT * FROM users ORDER BY display_name ASC, users.created_at DESC"
+/// );
+///
+/// assert!(matches!(
+///     Sort::parse_keys::<UserSort>("password"),
+///     Err(Error::UnknownSortColumn(key)) if key == "password"
+/// ));
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sort {
+    terms: Vec<SortTerm>,
+    nulls: Option<Nulls>,
+    tiebreaker: Option<SortTerm>,
+}
+
+impl Sort {
+    /// Parse `input`, looking up each key in `columns`.
+    ///
+    /// Whitespace around keys and empty keys are ignored, so an empty `input` gives an empty
+    /// sort order.
+    ///
+    /// ### Errors
+    /// Returns [`Error::UnknownSortColumn`] if a key isn't in `columns`.
+    pub fn parse<C>(input: &str, columns: &C) -> Result<Self, Error>
+    where
+        C: SortColumns + ?Sized,
+    {
+        let mut sort = Sort::default();
+        for term in input
+            .split(',')
+            .map(str::trim)
+            .filter(|term| !term.is_empty())
+        {
+            let (key, direction) = match term.strip_prefix('-') {
+                Some(key) => (key, Direction::Desc),
+                None => (term, Direction::Asc),
+            };
+            let column = columns
+                .sort_column(key)
+                .ok_or_else(|| Error::UnknownSortColumn(key.to_owned()))?;
+            sort.terms.push(SortTerm { column, direction });
+        }
+
+        Ok(sort)
+    }
+
+    /// Parse `input`, looking up each key with [`SortKey::from_sort_key()`].
+    ///
+    /// See [`Sort::parse()`] for details.
+    pub fn parse_keys<K: SortKey>(input: &str) -> Result<Self, Error> {
+        struct Keys<K>(PhantomData<K>);
+
+        impl<K: SortKey> SortColumns for Keys<K> {
+            fn sort_column(&self, key: &str) -> Option<&'static str> {
+                K::from_sort_key(key).map(|key| key.column())
+            }
+        }
+
+        Sort::parse(input, &Keys::<K>(PhantomData))
+    }
+
+    /// Put `NULL` values first or last in every column but the tiebreaker.
+    pub fn nulls(mut self, nulls: Nulls) -> Self {
+        self.nulls = Some(nulls);
+        self
+    }
+
+    /// Sort by `column` after the parsed columns, unless it is one of them already.
+    ///
+    /// `column` should be a unique, non-nullable column, such as the primary key.
+    pub fn tiebreaker(mut self, column: &'static str, direction: Direction) -> Self {
+        self.tiebreaker = Some(SortTerm { column, direction });
+        self
+    }
+
+    /// Whether there is no column to sort by, including the tiebreaker.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.tiebreaker.is_none()
+    }
+
+    /// Push ` ORDER BY a DESC, b ASC` with the columns of the sort order.
+    ///
+    /// Nothing is pushed if the sort order [is empty][Self::is_empty].
+    pub fn push_order_by<'args, DB: Database>(&self, query_builder: &mut QueryBuilder<'args, DB>) {
+        let native_nulls = Dialect::of::<DB>().has_nulls_ordering();
+
+        let tiebreaker = self
+            .tiebreaker
+            .filter(|tiebreaker| !self.terms.iter().any(|t| t.column == tiebreaker.column));
+        let terms = self.terms.iter().map(|term| (term, self.nulls));
+        let tiebreaker = tiebreaker.iter().map(|term| (term, None));
+
+        for (i, (term, nulls)) in terms.chain(tiebreaker).enumerate() {
+            query_builder.push(if i == 0 { " ORDER BY " } else { ", " });
+
+            if let (Some(nulls), false) = (nulls, native_nulls) {
+                let (null, not_null) = match nulls {
+                    Nulls::First => (0, 1),
+                    Nulls::Last => (1, 0),
+                };
+                query_builder.push(format_args!(
+                    "CASE WHEN {} IS NULL THEN {null} ELSE {not_null} END, ",
+                    term.column
+                ));
+            }
+
+            query_builder.push(term.column);
+            query_builder.push(" ");
+            query_builder.push(term.direction.keyword());
+
+            if let (Some(nulls), true) = (nulls, native_nulls) {
+                query_builder.push(match nulls {
+                    Nulls::First => " NULLS FIRST",
+                    Nulls::Last => " NULLS LAST",
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,298 @@
+//! A query builder that only accepts trusted raw SQL.
+
+use std::fmt::Debug;
+
+use sqlx::database::Database;
+use sqlx::encode::Encode;
+use sqlx::query::Query;
+use sqlx::types::Type;
+
+use crate::builder2::{BuiltQuery, QueryBuilder};
+use crate::cond::Cond;
+use crate::debug::DebugSql;
+use crate::error::Error;
+use crate::fragment::{Fragment, PushFragment};
+use crate::named::NamedArguments;
+use crate::raw::RawSql;
+use crate::tuple::BindTuple;
+
+/// A [`QueryBuilder`] that only accepts [`RawSql`] as raw SQL.
+///
+/// [`QueryBuilder::push()`] accepts anything that implements `Display`, so a `String` built from
+/// user input compiles without complaint. The methods of `StrictQueryBuilder` that push raw SQL
+/// only accept string literals and values explicitly wrapped in
+/// [`TrustedSql::assume_safe()`][crate::TrustedSql::assume_safe], and a plain [`QueryBuilder`]
+/// can't be pushed to it.
+///
+/// Identifiers can still be pushed with [`.push_ident()`][Self::push_ident], and values with
+/// the bind argument methods. [`Fragment`]s and [`Cond`]s are accepted as-is, and
+/// [`Fragment::new()`] and [`Fragment::push()`] accept anything that implements `Display`, like
+/// [`QueryBuilder::push()`]. Build fragments with [`fragment!()`][crate::fragment!] instead,
+/// whose `{x:raw}` placeholders are restricted to [`RawSql`]. Every place where SQL built at
+/// runtime can end up in a strict query is then a call to `assume_safe`,
+/// [`into_inner`][Self::into_inner], `Fragment::new` or `Fragment::push`.
+///
+/// ```rust
+/// # #[cfg(feature = "postgres")] {
+/// use sqlx::Postgres;
+/// use sqlx_fragment::{fragment, StrictQueryBuilder, TrustedSql};
+///
+/// let name = String::from("alice");
+/// let direction = if true { "ASC" } else { "DESC" };
+///
+/// let mut query_builder: StrictQueryBuilder<Postgres> = StrictQueryBuilder::new("SELECT * FROM ");
+/// query_builder
+///     .push_ident("users")
+///     .push(" WHERE name = ")
+///     .push_bind(name)
+///     .push_fragment(fragment!(" AND age > {}", 18))
+///     .push(TrustedSql::assume_safe(format!(" ORDER BY id {direction}")));
+///
+/// assert_eq!(
+///     query_builder.sql(),
+///     r#"SELECT * FROM "users" WHERE name = $1 AND age > $2 ORDER BY id ASC"#
+/// );
+/// # }
+/// ```
+///
+/// Pushing a `String` doesn't compile:
+///
+/// ```compile_fail
+/// # use sqlx_fragment::StrictQueryBuilder;
+/// let name = String::from("alice");
+/// let mut query_builder: StrictQueryBuilder<sqlx::Any> = StrictQueryBuilder::new("SELECT ");
+/// query_builder.push(format!("'{name}'"));
+/// ```
+///
+/// Neither does pushing a plain `QueryBuilder`:
+///
+/// ```compile_fail
+/// # use sqlx_fragment::builder2::QueryBuilder;
+/// # use sqlx_fragment::StrictQueryBuilder;
+/// let name = String::from("alice");
+/// let mut query_builder: StrictQueryBuilder<sqlx::Any> = StrictQueryBuilder::new("SELECT ");
+/// query_builder.push_fragment(QueryBuilder::new(format!("'{name}'")));
+/// ```
+pub struct StrictQueryBuilder<'args, DB: Database>(QueryBuilder<'args, DB>);
+
+impl<'args, DB: Database> Default for StrictQueryBuilder<'args, DB> {
+    fn default() -> Self {
+        StrictQueryBuilder(QueryBuilder::default())
+    }
+}
+
+impl<'args, DB: Database> StrictQueryBuilder<'args, DB> {
+    /// Start building a query with an initial SQL fragment, which may be an empty string.
+    pub fn new(init: impl RawSql) -> Self
+    where
+        <DB as Database>::Arguments<'args>: Default,
+    {
+        StrictQueryBuilder(QueryBuilder::new(init.to_string()))
+    }
+
+    /// Append trusted SQL to the query.
+    ///
+    /// See [`QueryBuilder::push()`].
+    pub fn push(&mut self, sql: impl RawSql) -> &mut Self {
+        self.0.push(sql);
+        self
+    }
+
+    /// Append a quoted identifier, such as a table or column name, to the query.
+    ///
+    /// See [`QueryBuilder::push_ident()`].
+    pub fn push_ident(&mut self, ident: &str) -> &mut Self {
+        self.0.push_ident(ident);
+        self
+    }
+
+    /// Append a qualified identifier, such as `schema.table.column`, quoting each part.
+    ///
+    /// See [`QueryBuilder::push_qualified_ident()`].
+    pub fn push_qualified_ident<I>(&mut self, parts: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.0.push_qualified_ident(parts);
+        self
+    }
+
+    /// Push a bind argument placeholder and bind a value to it.
+    ///
+    /// See [`QueryBuilder::push_bind()`].
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.0.push_bind(value);
+        self
+    }
+
+    /// Push a bind argument placeholder and bind a value to it, or return an error.
+    ///
+    /// See [`QueryBuilder::try_push_bind()`].
+    pub fn try_push_bind<T>(&mut self, value: T) -> Result<&mut Self, Error>
+    where
+        T: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.0.try_push_bind(value)?;
+        Ok(self)
+    }
+
+    /// Append trusted SQL with named parameters, such as `:name`, taking their values from
+    /// `arguments`.
+    ///
+    /// See [`QueryBuilder::push_named()`].
+    pub fn push_named<A>(&mut self, sql: impl RawSql, arguments: &A) -> Result<&mut Self, Error>
+    where
+        A: NamedArguments<'args, DB> + ?Sized,
+    {
+        self.0.push_named(sql, arguments)?;
+        Ok(self)
+    }
+
+    /// Push a parenthesized list of bind arguments, one per element of `tuple`.
+    ///
+    /// See [`QueryBuilder::push_bind_tuple()`].
+    pub fn push_bind_tuple(&mut self, tuple: impl BindTuple<'args, DB>) -> &mut Self {
+        self.0.push_bind_tuple(tuple);
+        self
+    }
+
+    /// Append a `column IN (...)` predicate, with one bind argument per value.
+    ///
+    /// See [`QueryBuilder::push_in()`].
+    pub fn push_in<I>(&mut self, column: impl RawSql, values: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.0.push_in(column, values);
+        self
+    }
+
+    /// Append a `column NOT IN (...)` predicate, with one bind argument per value.
+    ///
+    /// See [`QueryBuilder::push_not_in()`].
+    pub fn push_not_in<I>(&mut self, column: impl RawSql, values: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,
+    {
+        self.0.push_not_in(column, values);
+        self
+    }
+
+    /// Append a [`Fragment`] or the contents of another `StrictQueryBuilder`.
+    ///
+    /// See [`QueryBuilder::push_fragment()`].
+    pub fn push_fragment(&mut self, fragment: impl StrictFragment<'args, DB>) -> &mut Self {
+        self.0.push_fragment(fragment);
+        self
+    }
+
+    /// Append a [`Fragment`] or the contents of another `StrictQueryBuilder`, or return an error
+    /// and leave the query unchanged if it can't be pushed.
+    ///
+    /// See [`QueryBuilder::try_push_fragment()`].
+    pub fn try_push_fragment(
+        &mut self,
+        fragment: impl StrictFragment<'args, DB>,
+    ) -> Result<&mut Self, Error> {
+        self.0.try_push_fragment(fragment)?;
+        Ok(self)
+    }
+
+    /// Append a boolean condition built with [`Cond`].
+    ///
+    /// See [`QueryBuilder::push_condition()`].
+    pub fn push_condition(&mut self, condition: Cond<'args, DB>) -> &mut Self {
+        self.0.push_condition(condition);
+        self
+    }
+
+    /// Produce an executable query from this builder.
+    ///
+    /// See [`QueryBuilder::build()`].
+    pub fn build(&mut self) -> Query<'_, DB, <DB as Database>::Arguments<'args>> {
+        self.0.build()
+    }
+
+    /// Produce an executable query from this builder, or return an error if it can't be built.
+    ///
+    /// See [`QueryBuilder::try_build()`].
+    pub fn try_build(
+        &mut self,
+    ) -> Result<Query<'_, DB, <DB as Database>::Arguments<'args>>, Error> {
+        self.0.try_build()
+    }
+
+    /// Produce an owned, executable query from this builder.
+    ///
+    /// See [`QueryBuilder::into_query()`].
+    pub fn into_query(self) -> BuiltQuery<'args, DB> {
+        self.0.into_query()
+    }
+
+    /// Reset this builder to the state it was in immediately after [`new()`][Self::new].
+    pub fn reset(&mut self) -> &mut Self {
+        self.0.reset();
+        self
+    }
+
+    /// Get the current build SQL; **note**: may not be syntactically correct.
+    pub fn sql(&self) -> &str {
+        self.0.sql()
+    }
+
+    /// Get the current build SQL with every bind argument inlined as a literal, for logging and
+    /// debugging.
+    ///
+    /// See [`QueryBuilder::to_debug_sql()`].
+    pub fn to_debug_sql(&self) -> DebugSql {
+        self.0.to_debug_sql()
+    }
+
+    /// Unwrap the inner [`QueryBuilder`], which accepts any SQL.
+    ///
+    /// ### Warning
+    /// Like [`TrustedSql::assume_safe()`][crate::TrustedSql::assume_safe], this is an escape
+    /// hatch; everything pushed to the returned builder must be checked during a security review.
+    pub fn into_inner(self) -> QueryBuilder<'args, DB> {
+        self.0
+    }
+}
+
+impl<'args, DB: Database> PushFragment<'args, DB> for StrictQueryBuilder<'args, DB> {
+    fn try_push_to(self, query_builder: &mut QueryBuilder<'args, DB>) -> Result<(), Error> {
+        self.0.try_push_to(query_builder)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A fragment accepted by [`StrictQueryBuilder::push_fragment()`]: a [`Fragment`] or another
+/// [`StrictQueryBuilder`], but not a plain [`QueryBuilder`].
+///
+/// This trait is sealed and can't be implemented outside of this crate.
+pub trait StrictFragment<'args, DB: Database>: PushFragment<'args, DB> + private::Sealed {}
+
+impl<'args, DB: Database> StrictFragment<'args, DB> for Fragment<'args, DB> {}
+
+impl<'args, DB: Database> StrictFragment<'args, DB> for StrictQueryBuilder<'args, DB> {}
+
+mod private {
+    use sqlx::database::Database;
+
+    use crate::fragment::Fragment;
+    use crate::strict::StrictQueryBuilder;
+
+    pub trait Sealed {}
+
+    impl<'args, DB: Database> Sealed for Fragment<'args, DB> {}
+
+    impl<'args, DB: Database> Sealed for StrictQueryBuilder<'args, DB> {}
+}
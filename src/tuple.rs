@@ -0,0 +1,55 @@
+//! Tuples of bind arguments, for row constructors such as `($1, $2)`.
+
+use std::fmt::Debug;
+
+use sqlx::database::Database;
+use sqlx::encode::Encode;
+use sqlx::types::Type;
+
+use crate::builder2::Separated;
+
+/// A tuple whose elements can each be pushed with
+/// [`QueryBuilder::push_bind()`][crate::builder2::QueryBuilder::push_bind].
+///
+/// This is implemented for tuples of up to 16 elements. See
+/// [`QueryBuilder::push_bind_tuple()`][crate::builder2::QueryBuilder::push_bind_tuple].
+pub trait BindTuple<'args, DB: Database> {
+    /// The number of elements in the tuple.
+    const LEN: usize;
+
+    #[doc(hidden)]
+    fn push_binds(self, separated: &mut Separated<'_, 'args, DB, &'static str>);
+}
+
+macro_rules! impl_bind_tuple {
+    ($len:literal: $($T:ident $index:tt),+) => {
+        impl<'args, DB, $($T),+> BindTuple<'args, DB> for ($($T,)+)
+        where
+            DB: Database,
+            $($T: 'args + Send + Debug + Encode<'args, DB> + Type<DB>,)+
+        {
+            const LEN: usize = $len;
+
+            fn push_binds(self, separated: &mut Separated<'_, 'args, DB, &'static str>) {
+                $(separated.push_bind(self.$index);)+
+            }
+        }
+    };
+}
+
+impl_bind_tuple!(1: T0 0);
+impl_bind_tuple!(2: T0 0, T1 1);
+impl_bind_tuple!(3: T0 0, T1 1, T2 2);
+impl_bind_tuple!(4: T0 0, T1 1, T2 2, T3 3);
+impl_bind_tuple!(5: T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_bind_tuple!(6: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_bind_tuple!(7: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_bind_tuple!(8: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+impl_bind_tuple!(9: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+impl_bind_tuple!(10: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+impl_bind_tuple!(11: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+impl_bind_tuple!(12: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
+impl_bind_tuple!(13: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12);
+impl_bind_tuple!(14: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12, T13 13);
+impl_bind_tuple!(15: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12, T13 13, T14 14);
+impl_bind_tuple!(16: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11, T12 12, T13 13, T14 14, T15 15);
@@ -0,0 +1,159 @@
+//! Postgres bulk inserts with one array bind argument per column.
+
+use std::fmt::{Debug, Display};
+
+use sqlx::encode::Encode;
+use sqlx::postgres::Postgres;
+use sqlx::types::Type;
+use sqlx::TypeInfo;
+
+use crate::builder2::QueryBuilder;
+
+/// A row of values that can be transposed into one array per column, for
+/// [`QueryBuilder::push_unnest()`].
+///
+/// This is implemented for tuples of up to 12 elements, where each element type `T` can be
+/// bound as an array (`Vec<T>`).
+pub trait UnnestRow<'args> {
+    /// The number of columns in the row.
+    const COLUMNS: usize;
+
+    #[doc(hidden)]
+    type Arrays: Default;
+
+    #[doc(hidden)]
+    fn push_to_arrays(self, arrays: &mut Self::Arrays);
+
+    #[doc(hidden)]
+    fn bind_arrays(arrays: Self::Arrays, query_builder: &mut QueryBuilder<'args, Postgres>);
+}
+
+/// Bind `array` with an explicit cast to its array type, e.g. `$1::INT4[]`.
+fn bind_array<'args, T>(query_builder: &mut QueryBuilder<'args, Postgres>, array: T)
+where
+    T: 'args + Send + Debug + Encode<'args, Postgres> + Type<Postgres>,
+{
+    query_builder.push_bind(array);
+    query_builder.push(format_args!("::{}", T::type_info().name()));
+}
+
+macro_rules! impl_unnest_row {
+    ($count:literal: $($T:ident $index:tt),+) => {
+        impl<'args, $($T),+> UnnestRow<'args> for ($($T,)+)
+        where
+            $(Vec<$T>: 'args + Send + Debug + Encode<'args, Postgres> + Type<Postgres>,)+
+        {
+            const COLUMNS: usize = $count;
+
+            type Arrays = ($(Vec<$T>,)+);
+
+            fn push_to_arrays(self, arrays: &mut Self::Arrays) {
+                $(arrays.$index.push(self.$index);)+
+            }
+
+            fn bind_arrays(arrays: Self::Arrays, query_builder: &mut QueryBuilder<'args, Postgres>) {
+                $(
+                    if $index > 0 {
+                        query_builder.push(", ");
+                    }
+                    bind_array(query_builder, arrays.$index);
+                )+
+            }
+        }
+    };
+}
+
+impl_unnest_row!(1: T0 0);
+impl_unnest_row!(2: T0 0, T1 1);
+impl_unnest_row!(3: T0 0, T1 1, T2 2);
+impl_unnest_row!(4: T0 0, T1 1, T2 2, T3 3);
+impl_unnest_row!(5: T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_unnest_row!(6: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_unnest_row!(7: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_unnest_row!(8: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+impl_unnest_row!(9: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8);
+impl_unnest_row!(10: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9);
+impl_unnest_row!(11: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
+impl_unnest_row!(12: T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11);
+
+impl<'args> QueryBuilder<'args, Postgres> {
+    /// Push `UNNEST($1::T[], $2::U[], ...)`, with the values of `rows` transposed into one array
+    /// bind argument per column.
+    ///
+    /// Unlike [`.push_values()`][Self::push_values], the number of bind arguments doesn't
+    /// depend on the number of rows. See [`.push_insert_unnest()`][Self::push_insert_unnest] for
+    /// the common case of a bulk `INSERT`.
+    pub fn push_unnest<I>(&mut self, rows: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: UnnestRow<'args>,
+    {
+        let mut arrays = <I::Item as UnnestRow<'args>>::Arrays::default();
+        for row in rows {
+            row.push_to_arrays(&mut arrays);
+        }
+
+        self.push("UNNEST(");
+        <I::Item as UnnestRow<'args>>::bind_arrays(arrays, self);
+        self.push(")")
+    }
+
+    /// Push `INSERT INTO table (a, b, ...) SELECT * FROM UNNEST($1::T[], $2::U[], ...)`, inserting
+    /// every row of `rows` with one array bind argument per column.
+    ///
+    /// `table` and `columns` are pushed as-is, like [`.push()`][Self::push]. A suffix such as
+    /// `ON CONFLICT DO NOTHING` or `RETURNING id` can be pushed afterwards.
+    ///
+    /// ### Panics
+    /// If the number of `columns` doesn't match the number of values in each row.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::Postgres;
+    /// use sqlx_fragment::builder2::QueryBuilder;
+    ///
+    /// let users = (0..1000).map(|i| (i, format!("user{i}")));
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("");
+    /// query_builder.push_insert_unnest("users", ["id", "name"], users);
+    ///
+    /// assert_eq!(
+    ///     query_builder.sql(),
+    ///     "INSERT INTO users (id, name) SELECT * FROM UNNEST($1::INT4[], $2::TEXT[])"
+    /// );
+    /// # }
+    /// ```
+    pub fn push_insert_unnest<C, I>(
+        &mut self,
+        table: impl Display,
+        columns: C,
+        rows: I,
+    ) -> &mut Self
+    where
+        C: IntoIterator,
+        C::Item: Display,
+        I: IntoIterator,
+        I::Item: UnnestRow<'args>,
+    {
+        self.push("INSERT INTO ");
+        self.push(table);
+        self.push(" (");
+        let mut count = 0;
+        for column in columns {
+            if count > 0 {
+                self.push(", ");
+            }
+            self.push(column);
+            count += 1;
+        }
+        assert_eq!(
+            count,
+            <I::Item as UnnestRow<'args>>::COLUMNS,
+            "`push_insert_unnest()` was given {count} columns for rows of {} values",
+            <I::Item as UnnestRow<'args>>::COLUMNS
+        );
+        self.push(") SELECT * FROM ");
+
+        self.push_unnest(rows)
+    }
+}